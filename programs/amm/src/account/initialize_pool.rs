@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::state::pool::Pool;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub depositor: Signer<'info>,
+
+    pub token_a_mint: Account<'info, Mint>,
+
+    pub token_b_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<Pool>(),
+        seeds = [
+            b"pool",
+            token_a_mint.key().as_ref(),
+            token_b_mint.key().as_ref(),
+            depositor.key().as_ref()
+        ],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    // Anyone can create a PDA's associated token accounts ahead of time, so
+    // an existing vault must not block the pool.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_a_mint,
+        associated_token::authority = pool
+    )]
+    pub token_a_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_b_mint,
+        associated_token::authority = pool
+    )]
+    pub token_b_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = depositor
+    )]
+    pub depositor_token_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = depositor
+    )]
+    pub depositor_token_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
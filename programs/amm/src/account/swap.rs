@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::pool::Pool;
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"pool",
+            pool.token_a_mint.as_ref(),
+            pool.token_b_mint.as_ref(),
+            pool.depositor.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = token_a_vault,
+        has_one = token_b_vault
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub token_a_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub token_b_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = pool.token_a_mint,
+        token::authority = user
+    )]
+    pub user_token_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = pool.token_b_mint,
+        token::authority = user
+    )]
+    pub user_token_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum AmmError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+
+    #[msg("Slippage exceeded")]
+    SlippageExceeded,
+
+    #[msg("Numeric overflow")]
+    NumericOverflow,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::{
+    account::initialize_pool::InitializePool, error::AmmError, state::pool::PoolInitializedEvent,
+};
+
+pub fn handler(
+    ctx: Context<InitializePool>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<()> {
+    require_gt!(token_a_amount, 0, AmmError::InvalidAmount);
+    require_gt!(token_b_amount, 0, AmmError::InvalidAmount);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.token_a_mint = ctx.accounts.token_a_mint.key();
+    pool.token_b_mint = ctx.accounts.token_b_mint.key();
+    pool.token_a_vault = ctx.accounts.token_a_vault.key();
+    pool.token_b_vault = ctx.accounts.token_b_vault.key();
+    pool.depositor = ctx.accounts.depositor.key();
+    pool.bump = ctx.bumps.pool;
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.depositor_token_a,
+            &ctx.accounts.token_a_vault,
+            token_a_amount,
+        ),
+        (
+            &ctx.accounts.depositor_token_b,
+            &ctx.accounts.token_b_vault,
+            token_b_amount,
+        ),
+    ] {
+        let cpi_accounts = token::Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    emit!(PoolInitializedEvent {
+        pool: ctx.accounts.pool.key(),
+        token_a_mint: ctx.accounts.token_a_mint.key(),
+        token_b_mint: ctx.accounts.token_b_mint.key(),
+        depositor: ctx.accounts.depositor.key(),
+        token_a_amount,
+        token_b_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::{
+    account::swap::Swap,
+    error::AmmError,
+    state::pool::{SwapEvent, SWAP_FEE_BPS},
+};
+
+pub fn handler(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    min_amount_out: u64,
+    a_to_b: bool,
+) -> Result<()> {
+    require_gt!(amount_in, 0, AmmError::InvalidAmount);
+
+    let accounts = &ctx.accounts;
+    let (user_in, user_out, vault_in, vault_out) = if a_to_b {
+        (
+            &accounts.user_token_a,
+            &accounts.user_token_b,
+            &accounts.token_a_vault,
+            &accounts.token_b_vault,
+        )
+    } else {
+        (
+            &accounts.user_token_b,
+            &accounts.user_token_a,
+            &accounts.token_b_vault,
+            &accounts.token_a_vault,
+        )
+    };
+
+    let amount_in_after_fee = amount_in as u128 * (10_000 - SWAP_FEE_BPS) as u128 / 10_000;
+    let amount_out = (vault_out.amount as u128)
+        .checked_mul(amount_in_after_fee)
+        .and_then(|numerator| {
+            numerator.checked_div((vault_in.amount as u128).checked_add(amount_in_after_fee)?)
+        })
+        .and_then(|amount_out| u64::try_from(amount_out).ok())
+        .ok_or(AmmError::NumericOverflow)?;
+    require_gte!(amount_out, min_amount_out, AmmError::SlippageExceeded);
+    require_gt!(amount_out, 0, AmmError::InvalidAmount);
+
+    let cpi_accounts = token::Transfer {
+        from: user_in.to_account_info(),
+        to: vault_in.to_account_info(),
+        authority: accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount_in)?;
+
+    let pool = &accounts.pool;
+    let seeds: &[&[u8]] = &[
+        b"pool",
+        pool.token_a_mint.as_ref(),
+        pool.token_b_mint.as_ref(),
+        pool.depositor.as_ref(),
+        &[pool.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    let cpi_accounts = token::Transfer {
+        from: vault_out.to_account_info(),
+        to: user_out.to_account_info(),
+        authority: pool.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount_out)?;
+
+    emit!(SwapEvent {
+        pool: pool.key(),
+        user: accounts.user.key(),
+        a_to_b,
+        amount_in,
+        amount_out,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+#![allow(unexpected_cfgs)]
+
+mod account;
+pub mod error;
+mod instructions;
+pub mod state;
+use account::initialize_pool::*;
+use account::swap::*;
+use anchor_lang::prelude::*;
+
+declare_id!("E1DxwMRJ5yQ8nZapnc4vUAaqc63aTfPL584ypNuVwiFh");
+
+#[program]
+pub mod amm {
+    use super::*;
+
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        instructions::initialize_pool::handler(ctx, token_a_amount, token_b_amount)
+    }
+
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        min_amount_out: u64,
+        a_to_b: bool,
+    ) -> Result<()> {
+        instructions::swap::handler(ctx, amount_in, min_amount_out, a_to_b)
+    }
+}
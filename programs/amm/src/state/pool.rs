@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// Fee taken from the input side of every swap, left in the pool.
+pub const SWAP_FEE_BPS: u64 = 30;
+
+/// A constant-product pool over two mints. Liquidity deposited at creation is
+/// locked: there are no LP tokens and no way to withdraw it.
+///
+/// The depositor is part of the pool's seeds, so a pair can have many pools
+/// and nobody can take the address of a pool another depositor will seed.
+#[account]
+pub struct Pool {
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub depositor: Pubkey,
+    pub bump: u8,
+}
+
+#[event]
+pub struct PoolInitializedEvent {
+    pub pool: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub depositor: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+}
@@ -4,16 +4,19 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::state::{bonding_curve::BondingCurve, config::GlobalConfig};
+use crate::{
+    error::PumpError,
+    state::{
+        bonding_curve::BondingCurve, config::GlobalConfig, creator_vault::CreatorVault,
+        migration_vault::MigrationVault, referral::Referral, trader_stats::TraderStats,
+    },
+};
 
 #[derive(Accounts)]
 pub struct BuyTokens<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
-    /// CHECK: something
-    pub relayer: AccountInfo<'info>,
-
     #[account(
         mut,
         seeds = [b"global-config"],
@@ -21,15 +24,50 @@ pub struct BuyTokens<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    /// CHECK: Treasury is a raw `AccountInfo`
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ PumpError::IncorrectFeeRecipient
+    )]
+    pub treasury: AccountInfo<'info>,
+
     #[account(mut)]
     pub token_mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"bonding-curve", token_mint.key().as_ref(), bonding_curve.creator.as_ref()],
-        bump = bonding_curve.bump
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-vault", bonding_curve.key().as_ref()],
+        bump = creator_vault.bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref()],
+        bump = referral.bump,
+        constraint = referral.referrer != buyer.key() @ PumpError::SelfReferral
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + std::mem::size_of::<TraderStats>(),
+        seeds = [b"trader-stats", buyer.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Account<'info, TraderStats>,
 
     #[account(
         mut,
@@ -46,24 +84,19 @@ pub struct BuyTokens<'info> {
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
 
-    pub wsol_mint_account: Account<'info, Mint>,
-
     #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = wsol_mint_account,
-        associated_token::authority = relayer
+        mut,
+        seeds = [b"migration-vault", bonding_curve.key().as_ref()],
+        bump = migration_vault.bump
     )]
-    pub relayer_wsol_account: Account<'info, TokenAccount>,
+    pub migration_vault: Account<'info, MigrationVault>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
+        mut,
         associated_token::mint = token_mint,
-        associated_token::authority = relayer
+        associated_token::authority = migration_vault
     )]
-    pub relayer_token_account: Account<'info, TokenAccount>,
-
+    pub migration_vault_token_account: Account<'info, TokenAccount>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
 
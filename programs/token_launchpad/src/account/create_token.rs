@@ -3,11 +3,20 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::state::{
-    bonding_curve::BondingCurve,
-    config::{GlobalConfig, TOKEN_DECIMALS},
+    bonding_curve::{BondingCurve, CurveKind, LaunchParams},
+    config::GlobalConfig,
+    creator_vault::CreatorVault,
+    migration_vault::MigrationVault,
 };
 
 #[derive(Accounts)]
+#[instruction(
+    name: String,
+    symbol: String,
+    uri: String,
+    curve_kind: CurveKind,
+    params: LaunchParams
+)]
 pub struct CreateToken<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -29,7 +38,7 @@ pub struct CreateToken<'info> {
     #[account(
         init,
         payer = creator, 
-        mint::decimals = TOKEN_DECIMALS,
+        mint::decimals = params.decimals,
         mint::authority = bonding_curve
     )]
     pub token_mint: Account<'info, Mint>,
@@ -41,7 +50,16 @@ pub struct CreateToken<'info> {
         seeds = [b"bonding-curve", token_mint.key().as_ref(), creator.key().as_ref()],
         bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<CreatorVault>(),
+        seeds = [b"creator-vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
 
     #[account(
     init_if_needed,
@@ -51,6 +69,23 @@ pub struct CreateToken<'info> {
 )]
     pub bonding_curve_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<MigrationVault>(),
+        seeds = [b"migration-vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub migration_vault: Account<'info, MigrationVault>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_vault
+    )]
+    pub migration_vault_token_account: Account<'info, TokenAccount>,
+
     /// CHECK: SOMETHING
     #[account(mut)]
     pub metadata_account: UncheckedAccount<'info>,
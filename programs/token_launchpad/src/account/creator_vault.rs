@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    error::PumpError,
+    state::{bonding_curve::BondingCurve, creator_vault::CreatorVault},
+};
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ PumpError::NotAuthorized
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-vault", bonding_curve.key().as_ref()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+}
@@ -1,14 +1,28 @@
 use anchor_lang::prelude::*;
 
-use crate::state::config::GlobalConfig;
+use anchor_spl::token::Mint;
+
+use crate::{
+    error::PumpError,
+    program::TokenLaunchpad,
+    state::{bonding_curve::BondingCurve, config::GlobalConfig},
+};
 
 #[derive(Accounts)]
 pub struct InitializeGlobalConfig<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, TokenLaunchpad>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PumpError::NotAuthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
     #[account(
-        init_if_needed,
+        init,
         payer = admin,
         space = 8 + std::mem::size_of::<GlobalConfig>(),
         seeds = [b"global-config"],
@@ -18,3 +32,72 @@ pub struct InitializeGlobalConfig<'info> {
 
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+        has_one = authority @ PumpError::NotAuthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Still in the first-deployment layout, so it cannot be loaded as
+    /// a `GlobalConfig`. The handler checks its discriminator and authority.
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+        constraint = global_config.pending_authority == Some(new_authority.key()) @ PumpError::NotAuthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBondingCurve<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global-config"],
+        bump = global_config.bump,
+        has_one = authority @ PumpError::NotAuthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+}
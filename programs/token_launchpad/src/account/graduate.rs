@@ -1,21 +1,82 @@
+use amm::program::Amm;
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
-
-use crate::state::{
-    bonding_curve::{BondingCurve},
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
 };
 
+use crate::state::{bonding_curve::BondingCurve, migration_vault::MigrationVault};
+
 #[derive(Accounts)]
 pub struct Graduate<'info> {
     pub token_mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"bonding-curve", token_mint.key().as_ref(), bonding_curve.creator.as_ref()],
-        bump = bonding_curve.bump,
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump,
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
 
+    /// Anyone can graduate a `Pending` curve; the payer only funds the new
+    /// accounts.
     #[account(mut)]
-    pub relayer: Signer<'info>,
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"migration-vault", bonding_curve.key().as_ref()],
+        bump = migration_vault.bump
+    )]
+    pub migration_vault: Account<'info, MigrationVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_vault
+    )]
+    pub migration_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = token::spl_token::native_mint::ID)]
+    pub wsol_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = migration_vault
+    )]
+    pub migration_vault_wsol_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Created by the AMM during the CPI and checked after it. The
+    /// migration vault is the depositor, so only this program can create it.
+    #[account(
+        mut,
+        seeds = [
+            b"pool",
+            token_mint.key().as_ref(),
+            wsol_mint.key().as_ref(),
+            migration_vault.key().as_ref()
+        ],
+        bump,
+        seeds::program = amm_program.key()
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the AMM during the CPI
+    #[account(mut)]
+    pub pool_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the AMM during the CPI
+    #[account(mut)]
+    pub pool_wsol_vault: UncheckedAccount<'info>,
+
+    pub amm_program: Program<'info, Amm>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
 }
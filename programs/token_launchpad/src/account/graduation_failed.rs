@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{bonding_curve::BondingCurve, migration_vault::MigrationVault};
+
+#[derive(Accounts)]
+pub struct GraduationFailed<'info> {
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"migration-vault", bonding_curve.key().as_ref()],
+        bump = migration_vault.bump
+    )]
+    pub migration_vault: Account<'info, MigrationVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = migration_vault
+    )]
+    pub migration_vault_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"migration-vault", bonding_curve.key().as_ref()],
+        bump = migration_vault.bump
+    )]
+    pub migration_vault: Account<'info, MigrationVault>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = holder
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
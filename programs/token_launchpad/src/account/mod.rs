@@ -1,6 +1,9 @@
 pub mod buy_tokens;
 pub mod create_token;
+pub mod creator_vault;
 pub mod global_config;
 pub mod graduate;
+pub mod graduation_failed;
+pub mod quote_trade;
+pub mod referral;
 pub mod sell_tokens;
-pub mod delete_program_data;
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{bonding_curve::BondingCurve, config::GlobalConfig, trader_stats::TraderStats};
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    #[account(
+        seeds = [b"global-config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    /// CHECK: Only keys `trader_stats`; quotes are read-only, so the trader
+    /// need not sign.
+    pub trader: UncheckedAccount<'info>,
+
+    /// Applies the trader's fee tier discount when present.
+    #[account(
+        seeds = [b"trader-stats", trader.key().as_ref()],
+        bump = trader_stats.bump
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+}
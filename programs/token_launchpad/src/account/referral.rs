@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpError, state::referral::Referral};
+
+#[derive(Accounts)]
+pub struct CreateReferral<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + std::mem::size_of::<Referral>(),
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump = referral.bump,
+        has_one = referrer @ PumpError::NotAuthorized
+    )]
+    pub referral: Account<'info, Referral>,
+}
@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::{bonding_curve::BondingCurve, config::GlobalConfig};
+use crate::{
+    error::PumpError,
+    state::{
+        bonding_curve::BondingCurve, config::GlobalConfig, creator_vault::CreatorVault,
+        referral::Referral, trader_stats::TraderStats,
+    },
+};
 
 #[derive(Accounts)]
 pub struct SellTokens<'info> {
@@ -12,10 +18,38 @@ pub struct SellTokens<'info> {
 
     #[account(
         mut,
-        seeds = [b"bonding-curve", token_mint.key().as_ref(), bonding_curve.creator.as_ref()],
-        bump = bonding_curve.bump
+        seeds = [
+            b"bonding-curve",
+            token_mint.key().as_ref(),
+            bonding_curve.load()?.creator.as_ref()
+        ],
+        bump = bonding_curve.load()?.bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"creator-vault", bonding_curve.key().as_ref()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref()],
+        bump = referral.bump,
+        constraint = referral.referrer != seller.key() @ PumpError::SelfReferral
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + std::mem::size_of::<TraderStats>(),
+        seeds = [b"trader-stats", seller.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Account<'info, TraderStats>,
 
     #[account(
         mut,
@@ -24,10 +58,25 @@ pub struct SellTokens<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
-    #[account(mut)]
+    /// CHECK: Treasury is a raw `AccountInfo`
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ PumpError::IncorrectFeeRecipient
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = seller
+    )]
     pub seller_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve
+    )]
     pub bonding_curve_token_account: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
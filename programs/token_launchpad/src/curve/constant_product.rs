@@ -0,0 +1,55 @@
+use crate::{
+    curve::{mul_div, Curve, Rounding, WAD},
+    math::{
+        calculate_sol_in, calculate_sol_out, calculate_tokens_in, calculate_tokens_out, MathResult,
+    },
+};
+
+/// `x * y = k` over the curve's virtual reserves.
+pub struct ConstantProduct {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+impl Curve for ConstantProduct {
+    fn tokens_out(&self, sol_in: u64) -> MathResult<u64> {
+        calculate_tokens_out(
+            sol_in,
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+        )
+    }
+
+    fn sol_out(&self, tokens_in: u64) -> MathResult<u64> {
+        calculate_sol_out(
+            tokens_in,
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+        )
+    }
+
+    fn sol_in(&self, tokens_out: u64) -> MathResult<u64> {
+        calculate_sol_in(
+            tokens_out,
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+        )
+    }
+
+    fn tokens_in(&self, sol_out: u64) -> MathResult<u64> {
+        calculate_tokens_in(
+            sol_out,
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+        )
+    }
+
+    fn spot_price(&self) -> MathResult<u128> {
+        mul_div(
+            self.virtual_sol_reserves as u128,
+            WAD,
+            self.virtual_token_reserves as u128,
+            Rounding::Down,
+        )
+    }
+}
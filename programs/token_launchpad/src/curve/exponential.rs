@@ -0,0 +1,192 @@
+use crate::{
+    curve::{mul_div, Rounding, SupplyCurve, WAD},
+    math::{MathError, MathResult},
+};
+
+/// ln(2) scaled by `WAD`.
+const LN2_WAD: u128 = 693_147_180_559_945_309;
+
+/// Price starts at `initial_virtual_sol_reserves / initial_virtual_token_reserves`
+/// and doubles every `initial_virtual_token_reserves` tokens sold.
+pub struct Exponential {
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+impl SupplyCurve for Exponential {
+    fn sold(&self) -> u64 {
+        self.initial_virtual_token_reserves
+            .saturating_sub(self.virtual_token_reserves)
+    }
+
+    fn remaining(&self) -> u64 {
+        self.virtual_token_reserves
+    }
+
+    // raised(s) = V * (2^(s / D) - 1) / ln(2)
+    fn raised_at(&self, sold: u128, rounding: Rounding) -> MathResult<u128> {
+        let v = self.initial_virtual_sol_reserves as u128;
+        let d = self.initial_virtual_token_reserves as u128;
+
+        let exponent = mul_div(sold, WAD, d, rounding)?;
+        let growth = exp2_wad(exponent)? - WAD;
+        let raised = mul_div(growth, v, LN2_WAD, rounding)?;
+
+        Ok(match rounding {
+            Rounding::Down => raised,
+            Rounding::Up => raised + 1,
+        })
+    }
+
+    // sold(r) = D * log2(1 + r * ln(2) / V)
+    fn sold_at(&self, raised: u128, rounding: Rounding) -> MathResult<u128> {
+        let v = self.initial_virtual_sol_reserves as u128;
+        let d = self.initial_virtual_token_reserves as u128;
+
+        let growth = mul_div(raised, LN2_WAD, v, rounding)?;
+        let exponent = log2_wad(WAD.checked_add(growth).ok_or(MathError::NumericOverflow)?)?;
+        let sold = mul_div(exponent, d, WAD, rounding)?;
+
+        Ok(match rounding {
+            Rounding::Down => sold.saturating_sub(1),
+            Rounding::Up => sold + 1,
+        })
+    }
+
+    fn price_at(&self, sold: u128) -> MathResult<u128> {
+        let v = self.initial_virtual_sol_reserves as u128;
+        let d = self.initial_virtual_token_reserves as u128;
+
+        let start = mul_div(v, WAD, d, Rounding::Down)?;
+        let growth = exp2_wad(mul_div(sold, WAD, d, Rounding::Down)?)?;
+
+        mul_div(start, growth, WAD, Rounding::Down)
+    }
+}
+
+/// `2^x` for `x` scaled by `WAD`, via the Taylor series of `e^(frac * ln 2)`.
+fn exp2_wad(x: u128) -> MathResult<u128> {
+    let whole = x / WAD;
+    let frac = x % WAD;
+
+    if whole >= 64 {
+        return Err(MathError::NumericOverflow);
+    }
+
+    let z = frac * LN2_WAD / WAD;
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut k = 1u128;
+    while term > 0 {
+        term = term * z / (k * WAD);
+        sum += term;
+        k += 1;
+    }
+
+    sum.checked_mul(1u128 << whole)
+        .ok_or(MathError::NumericOverflow)
+}
+
+/// `log2(x)` for `x >= 1`, both scaled by `WAD`, via repeated squaring.
+fn log2_wad(x: u128) -> MathResult<u128> {
+    if x < WAD {
+        return Err(MathError::InvalidConstant);
+    }
+
+    let whole = 127 - (x / WAD).leading_zeros();
+    let mut result = whole as u128 * WAD;
+    let mut y = x >> whole;
+    let mut delta = WAD / 2;
+    while delta > 0 {
+        y = y * y / WAD;
+        if y >= 2 * WAD {
+            result += delta;
+            y /= 2;
+        }
+        delta /= 2;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::Curve;
+
+    const V: u64 = 30_000_000_000;
+    const D: u64 = 1_073_000_000_000_000;
+
+    fn curve(sold: u64) -> Exponential {
+        Exponential {
+            initial_virtual_sol_reserves: V,
+            initial_virtual_token_reserves: D,
+            virtual_token_reserves: D - sold,
+        }
+    }
+
+    fn assert_close(actual: u128, expected: u128) {
+        let diff = actual.abs_diff(expected);
+        assert!(
+            diff <= expected / 1_000_000_000_000,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn exp2_wad_matches_known_powers() {
+        assert_eq!(exp2_wad(0).unwrap(), WAD);
+        assert_eq!(exp2_wad(3 * WAD).unwrap(), 8 * WAD);
+        assert_close(exp2_wad(WAD / 2).unwrap(), 1_414_213_562_373_095_048);
+        assert!(exp2_wad(64 * WAD).is_err());
+    }
+
+    #[test]
+    fn log2_wad_matches_known_logs() {
+        assert_eq!(log2_wad(WAD).unwrap(), 0);
+        assert_eq!(log2_wad(8 * WAD).unwrap(), 3 * WAD);
+        assert_close(log2_wad(3 * WAD).unwrap(), 1_584_962_500_721_156_181);
+        assert!(log2_wad(WAD - 1).is_err());
+    }
+
+    #[test]
+    fn log2_wad_inverts_exp2_wad() {
+        for x in [WAD / 7, WAD, 5 * WAD / 2, 20 * WAD] {
+            assert_close(log2_wad(exp2_wad(x).unwrap()).unwrap(), x);
+        }
+    }
+
+    #[test]
+    fn sold_at_inverts_raised_at() {
+        let curve = curve(0);
+        for sold in [1_000_000u128, D as u128 / 3, D as u128, 5 * D as u128] {
+            let down = curve.raised_at(sold, Rounding::Down).unwrap();
+            let up = curve.raised_at(sold, Rounding::Up).unwrap();
+            assert!(curve.sold_at(down, Rounding::Down).unwrap() <= sold);
+            assert!(curve.sold_at(up, Rounding::Up).unwrap() >= sold);
+        }
+    }
+
+    #[test]
+    fn round_trip_never_profits() {
+        for sold in [0, D / 10, D / 2, D - D / 10] {
+            let curve = curve(sold);
+            let tokens = curve.tokens_out(1_000_000_000).unwrap();
+            let after = Exponential {
+                virtual_token_reserves: curve.virtual_token_reserves - tokens,
+                ..curve
+            };
+            assert!(after.sol_out(tokens).unwrap() <= 1_000_000_000);
+        }
+    }
+
+    #[test]
+    fn tokens_in_covers_requested_sol() {
+        let curve = curve(D / 2);
+        for sol_out in [1, 1_000_000, 1_000_000_000, 10_000_000_000] {
+            let tokens_in = curve.tokens_in(sol_out).unwrap();
+            assert!(curve.sol_out(tokens_in).unwrap() >= sol_out);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    curve::{mul_div, sqrt, Rounding, SupplyCurve, WAD},
+    math::{MathError, MathResult},
+};
+
+/// Price starts at `initial_virtual_sol_reserves / initial_virtual_token_reserves`
+/// and rises linearly, doubling once `initial_virtual_token_reserves` are sold.
+pub struct Linear {
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+impl SupplyCurve for Linear {
+    fn sold(&self) -> u64 {
+        self.initial_virtual_token_reserves
+            .saturating_sub(self.virtual_token_reserves)
+    }
+
+    fn remaining(&self) -> u64 {
+        self.virtual_token_reserves
+    }
+
+    // raised(s) = V * s * (2D + s) / (2D^2)
+    fn raised_at(&self, sold: u128, rounding: Rounding) -> MathResult<u128> {
+        let v = self.initial_virtual_sol_reserves as u128;
+        let d = self.initial_virtual_token_reserves as u128;
+
+        let two_d = d.checked_mul(2).ok_or(MathError::NumericOverflow)?;
+        let area = mul_div(
+            sold,
+            two_d.checked_add(sold).ok_or(MathError::NumericOverflow)?,
+            two_d,
+            rounding,
+        )?;
+
+        mul_div(area, v, d, rounding)
+    }
+
+    // sold(r) = D * (sqrt(1 + 2r / V) - 1), with the root taken in WAD so
+    // no intermediate scales with D^2.
+    fn sold_at(&self, raised: u128, rounding: Rounding) -> MathResult<u128> {
+        let v = self.initial_virtual_sol_reserves as u128;
+        let d = self.initial_virtual_token_reserves as u128;
+
+        let growth = mul_div(
+            raised.checked_mul(2).ok_or(MathError::NumericOverflow)?,
+            WAD,
+            v,
+            rounding,
+        )?;
+        let root = sqrt_wad(
+            WAD.checked_add(growth).ok_or(MathError::NumericOverflow)?,
+            rounding,
+        );
+
+        mul_div(d, root.saturating_sub(WAD), WAD, rounding)
+    }
+
+    fn price_at(&self, sold: u128) -> MathResult<u128> {
+        let v = self.initial_virtual_sol_reserves as u128;
+        let d = self.initial_virtual_token_reserves as u128;
+
+        let start = mul_div(v, WAD, d, Rounding::Down)?;
+        mul_div(
+            start,
+            d.checked_add(sold).ok_or(MathError::NumericOverflow)?,
+            d,
+            Rounding::Down,
+        )
+    }
+}
+
+/// `sqrt(x)` for `x` scaled by `WAD`. Values too large to rescale in place
+/// fall back to rooting the unscaled value, which is still exact to 1e-9.
+fn sqrt_wad(x: u128, rounding: Rounding) -> u128 {
+    const SQRT_WAD: u128 = 1_000_000_000;
+
+    match x.checked_mul(WAD) {
+        Some(scaled) => sqrt(scaled, rounding),
+        None => sqrt(x, rounding) * SQRT_WAD,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::Curve;
+
+    const V: u64 = 30_000_000_000;
+    const D: u64 = 1_073_000_000_000_000;
+
+    fn curve(sold: u64) -> Linear {
+        Linear {
+            initial_virtual_sol_reserves: V,
+            initial_virtual_token_reserves: D,
+            virtual_token_reserves: D - sold,
+        }
+    }
+
+    #[test]
+    fn sqrt_wad_matches_known_roots() {
+        assert_eq!(sqrt_wad(4 * WAD, Rounding::Down), 2 * WAD);
+        assert_eq!(sqrt_wad(WAD / 4, Rounding::Down), WAD / 2);
+        assert_eq!(sqrt_wad(u128::MAX / 4, Rounding::Down) / WAD, 9_223_372_036);
+    }
+
+    #[test]
+    fn sold_at_inverts_raised_at() {
+        let curve = curve(0);
+        for sold in [1u128, 1_000_000, D as u128 / 3, D as u128, 5 * D as u128] {
+            let down = curve.raised_at(sold, Rounding::Down).unwrap();
+            let up = curve.raised_at(sold, Rounding::Up).unwrap();
+            assert!(curve.sold_at(down, Rounding::Down).unwrap() <= sold);
+            assert!(curve.sold_at(up, Rounding::Up).unwrap() >= sold);
+        }
+    }
+
+    #[test]
+    fn sold_at_does_not_overflow_for_large_supply() {
+        let curve = Linear {
+            initial_virtual_sol_reserves: 1_000_000_000,
+            initial_virtual_token_reserves: 10_000_000_000_000_000_000,
+            virtual_token_reserves: 10_000_000_000_000_000_000,
+        };
+        let sold = curve.sold_at(2_000_000_000, Rounding::Up).unwrap();
+        assert!(sold > 0);
+    }
+
+    #[test]
+    fn round_trip_never_profits() {
+        for sold in [0, D / 10, D / 2, D - D / 10] {
+            let curve = curve(sold);
+            let tokens = curve.tokens_out(1_000_000_000).unwrap();
+            let after = Linear {
+                virtual_token_reserves: curve.virtual_token_reserves - tokens,
+                ..curve
+            };
+            assert!(after.sol_out(tokens).unwrap() <= 1_000_000_000);
+        }
+    }
+
+    #[test]
+    fn tokens_in_covers_requested_sol() {
+        let curve = curve(D / 2);
+        for sol_out in [1, 1_000_000, 1_000_000_000, 10_000_000_000] {
+            let tokens_in = curve.tokens_in(sol_out).unwrap();
+            assert!(curve.sol_out(tokens_in).unwrap() >= sol_out);
+        }
+    }
+
+    #[test]
+    fn price_doubles_after_initial_reserves_sold() {
+        let curve = curve(0);
+        let start = curve.price_at(0).unwrap();
+        assert_eq!(curve.price_at(D as u128).unwrap(), 2 * start);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    math::{MathError, MathResult},
+    state::bonding_curve::CurveKind,
+};
+
+pub mod constant_product;
+pub mod exponential;
+pub mod linear;
+
+use constant_product::ConstantProduct;
+use exponential::Exponential;
+use linear::Linear;
+
+/// Fixed-point scale used for prices and the exponential curve.
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+
+const MAX_INVERSE_STEPS: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Trade maths for a bonding curve shape. Inputs are always rounded up and
+/// outputs rounded down so that every trade favors the curve.
+pub trait Curve {
+    /// Tokens released for `sol_in` lamports.
+    fn tokens_out(&self, sol_in: u64) -> MathResult<u64>;
+
+    /// Lamports released for `tokens_in` tokens.
+    fn sol_out(&self, tokens_in: u64) -> MathResult<u64>;
+
+    /// Lamports needed to release exactly `tokens_out` tokens.
+    fn sol_in(&self, tokens_out: u64) -> MathResult<u64>;
+
+    /// Tokens needed to release exactly `sol_out` lamports.
+    fn tokens_in(&self, sol_out: u64) -> MathResult<u64>;
+
+    /// Marginal price in lamports per base token unit, scaled by `WAD`.
+    fn spot_price(&self) -> MathResult<u128>;
+}
+
+/// A curve defined by the lamports raised as a function of tokens sold.
+/// Implementors get `Curve` for free.
+pub trait SupplyCurve {
+    /// Tokens sold off the curve so far.
+    fn sold(&self) -> u64;
+
+    /// Tokens the curve can still release.
+    fn remaining(&self) -> u64;
+
+    /// Lamports raised once `sold` tokens have been sold.
+    fn raised_at(&self, sold: u128, rounding: Rounding) -> MathResult<u128>;
+
+    /// Tokens sold once `raised` lamports have been raised.
+    fn sold_at(&self, raised: u128, rounding: Rounding) -> MathResult<u128>;
+
+    /// Marginal price at `sold`, scaled by `WAD`.
+    fn price_at(&self, sold: u128) -> MathResult<u128>;
+}
+
+/// Any supported curve shape. Dispatching through an enum keeps the trade
+/// path free of heap allocations.
+pub enum AnyCurve {
+    ConstantProduct(ConstantProduct),
+    Linear(Linear),
+    Exponential(Exponential),
+}
+
+impl AnyCurve {
+    pub fn new(
+        curve_kind: CurveKind,
+        initial_virtual_sol_reserves: u64,
+        initial_virtual_token_reserves: u64,
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+    ) -> Self {
+        match curve_kind {
+            CurveKind::ConstantProduct => Self::ConstantProduct(ConstantProduct {
+                virtual_sol_reserves,
+                virtual_token_reserves,
+            }),
+            CurveKind::Linear => Self::Linear(Linear {
+                initial_virtual_sol_reserves,
+                initial_virtual_token_reserves,
+                virtual_token_reserves,
+            }),
+            CurveKind::Exponential => Self::Exponential(Exponential {
+                initial_virtual_sol_reserves,
+                initial_virtual_token_reserves,
+                virtual_token_reserves,
+            }),
+        }
+    }
+
+    fn as_curve(&self) -> &dyn Curve {
+        match self {
+            Self::ConstantProduct(curve) => curve,
+            Self::Linear(curve) => curve,
+            Self::Exponential(curve) => curve,
+        }
+    }
+}
+
+impl Curve for AnyCurve {
+    fn tokens_out(&self, sol_in: u64) -> MathResult<u64> {
+        self.as_curve().tokens_out(sol_in)
+    }
+
+    fn sol_out(&self, tokens_in: u64) -> MathResult<u64> {
+        self.as_curve().sol_out(tokens_in)
+    }
+
+    fn sol_in(&self, tokens_out: u64) -> MathResult<u64> {
+        self.as_curve().sol_in(tokens_out)
+    }
+
+    fn tokens_in(&self, sol_out: u64) -> MathResult<u64> {
+        self.as_curve().tokens_in(sol_out)
+    }
+
+    fn spot_price(&self) -> MathResult<u128> {
+        self.as_curve().spot_price()
+    }
+}
+
+impl<T: SupplyCurve> Curve for T {
+    fn tokens_out(&self, sol_in: u64) -> MathResult<u64> {
+        let sold = self.sold() as u128;
+        let raised = self
+            .raised_at(sold, Rounding::Down)?
+            .checked_add(sol_in as u128)
+            .ok_or(MathError::NumericOverflow)?;
+        let new_sold = self.sold_at(raised, Rounding::Down)?;
+
+        to_u64(new_sold.saturating_sub(sold).min(self.remaining() as u128))
+    }
+
+    fn sol_out(&self, tokens_in: u64) -> MathResult<u64> {
+        let sold = self.sold() as u128;
+        let new_sold = sold
+            .checked_sub(tokens_in as u128)
+            .ok_or(MathError::InsufficientTokens)?;
+        let sol_out = self
+            .raised_at(sold, Rounding::Down)?
+            .saturating_sub(self.raised_at(new_sold, Rounding::Up)?);
+
+        to_u64(sol_out)
+    }
+
+    fn sol_in(&self, tokens_out: u64) -> MathResult<u64> {
+        if tokens_out > self.remaining() {
+            return Err(MathError::InsufficientTokens);
+        }
+
+        let sold = self.sold() as u128;
+        let new_sold = sold
+            .checked_add(tokens_out as u128)
+            .ok_or(MathError::NumericOverflow)?;
+        let mut sol_in = to_u64(
+            self.raised_at(new_sold, Rounding::Up)?
+                .saturating_sub(self.raised_at(sold, Rounding::Down)?),
+        )?;
+
+        // `sold_at` can land a unit short on large supplies, so top up until
+        // paying `sol_in` forward really releases `tokens_out`.
+        for _ in 0..MAX_INVERSE_STEPS {
+            if self.tokens_out(sol_in)? >= tokens_out {
+                break;
+            }
+            sol_in = sol_in.checked_add(1).ok_or(MathError::NumericOverflow)?;
+        }
+        if self.tokens_out(sol_in)? < tokens_out {
+            return Err(MathError::InvalidTokensCalculation);
+        }
+
+        Ok(sol_in)
+    }
+
+    fn tokens_in(&self, sol_out: u64) -> MathResult<u64> {
+        let sold = self.sold() as u128;
+        let new_raised = self
+            .raised_at(sold, Rounding::Down)?
+            .checked_sub(sol_out as u128)
+            .ok_or(MathError::InsufficientSol)?;
+
+        // The inverse is not exact, so step back until selling down to
+        // `new_sold` really pays out at least `sol_out`.
+        let mut target = new_raised;
+        let mut new_sold = self.sold_at(target, Rounding::Down)?;
+        for _ in 0..MAX_INVERSE_STEPS {
+            let raised = self.raised_at(new_sold, Rounding::Up)?;
+            if raised <= new_raised {
+                break;
+            }
+            target = target.saturating_sub(raised - new_raised);
+            new_sold = self.sold_at(target, Rounding::Down)?;
+        }
+        if self.raised_at(new_sold, Rounding::Up)? > new_raised {
+            return Err(MathError::InvalidTokensCalculation);
+        }
+
+        to_u64(sold.saturating_sub(new_sold))
+    }
+
+    fn spot_price(&self) -> MathResult<u128> {
+        self.price_at(self.sold() as u128)
+    }
+}
+
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> MathResult<u128> {
+    if denominator == 0 {
+        return Err(MathError::DivideByZero);
+    }
+
+    let product = a.checked_mul(b).ok_or(MathError::NumericOverflow)?;
+    let quotient = product / denominator;
+
+    if rounding == Rounding::Up && product % denominator != 0 {
+        return Ok(quotient + 1);
+    }
+
+    Ok(quotient)
+}
+
+pub fn sqrt(n: u128, rounding: Rounding) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+
+    if rounding == Rounding::Up && x * x != n {
+        return x + 1;
+    }
+
+    x
+}
+
+pub fn to_u64(value: u128) -> MathResult<u64> {
+    u64::try_from(value).map_err(|_| MathError::NumericOverflow)
+}
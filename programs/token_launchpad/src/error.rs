@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::math::MathError;
+
 #[error_code]
 pub enum PumpError {
     #[msg("Not authorized address")]
@@ -76,4 +78,72 @@ pub enum PumpError {
 
     #[msg("Token is not active")]
     TokenNotActive,
+
+    #[msg("Platform is paused")]
+    PlatformPaused,
+
+    #[msg("Trading on this curve is paused")]
+    CurvePaused,
+
+    // No longer raised since `graduate` became permissionless. Kept so the
+    // codes of the variants below do not shift.
+    #[msg("Relayer is not the one in the config")]
+    InvalidRelayer,
+
+    #[msg("Traders cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("Launch parameters are outside the configured limits")]
+    LaunchParamsOutOfRange,
+
+    #[msg("Curve and LP supply tranches are inconsistent")]
+    InvalidSupplyAllocation,
+
+    #[msg("Trade moves the price more than allowed")]
+    PriceImpactExceeded,
+
+    #[msg("Pool reserves do not match the migrated amounts")]
+    PoolReservesMismatch,
+
+    #[msg("Graduation deadline has not passed")]
+    GraduationDeadlineNotReached,
+
+    #[msg("Graduation has not failed")]
+    GraduationNotFailed,
+
+    #[msg("Pool does not match the launch")]
+    InvalidPool,
+
+    #[msg("Unknown curve kind")]
+    InvalidCurveKind,
+
+    #[msg("Trade would push the curve past the graduation threshold")]
+    GraduationThresholdExceeded,
+
+    #[msg("Global config is already in the current layout")]
+    ConfigAlreadyMigrated,
+}
+
+impl From<MathError> for PumpError {
+    fn from(error: MathError) -> Self {
+        match error {
+            MathError::InsufficientSol => PumpError::InsufficientSol,
+            MathError::IncorrectValue => PumpError::IncorrectValue,
+            MathError::OverflowOrUnderflowOccurred => PumpError::OverflowOrUnderflowOccurred,
+            MathError::InsufficientTokens => PumpError::InsufficientTokens,
+            MathError::InvalidVirtualSolReserves => PumpError::InvalidVirtualSolReserves,
+            MathError::InvalidVirtualTokenReserves => PumpError::InvalidVirtualTokenReserves,
+            MathError::InvalidConstant => PumpError::InvalidConstant,
+            MathError::InvalidTokensCalculation => PumpError::InvalidTokensCalculation,
+            MathError::NumericOverflow => PumpError::NumericOverflow,
+            MathError::DivideByZero => PumpError::DivideByZero,
+            MathError::GraduationThresholdExceeded => PumpError::GraduationThresholdExceeded,
+        }
+    }
+}
+
+impl From<MathError> for Error {
+    fn from(error: MathError) -> Self {
+        PumpError::from(error).into()
+    }
 }
@@ -1,48 +1,68 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::spl_associated_token_account, token};
+use anchor_spl::token;
 
 use crate::{
     account::buy_tokens::BuyTokens,
     error::PumpError,
     state::{bonding_curve::GraduationState, pool_request::CreatePoolRequestEvent},
 };
-pub fn graduate_internal(ctx: Context<BuyTokens>) -> Result<()> {
-    require!(
-        ctx.accounts.bonding_curve.graduated == GraduationState::Active
-            || ctx.accounts.bonding_curve.graduated == GraduationState::Pending,
-        PumpError::TokenGraduated
-    );
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
+/// Closes the curve once it sells out or reaches the graduation threshold.
+/// Only the tokens needed to open the pool at the curve's final price are
+/// kept; the rest is burned, and the reserves are escrowed in the migration
+/// vault while the curve waits in `Pending` for `graduate`.
+pub fn graduate_internal(ctx: Context<BuyTokens>) -> Result<()> {
     let token_mint = ctx.accounts.token_mint.key();
-    let creator_key = bonding_curve.creator;
+    let (creator_key, bump, real_sol, token_amount, burn_amount) = {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        require!(
+            bonding_curve.graduation_state() == GraduationState::Active,
+            PumpError::TokenGraduated
+        );
+
+        let token_amount = bonding_curve.lp_tokens_for(bonding_curve.real_sol_reserves)?;
+        let burn_amount = bonding_curve
+            .lp_token_reserves
+            .checked_add(bonding_curve.real_token_reserves)
+            .and_then(|available| available.checked_sub(token_amount))
+            .ok_or(PumpError::InvalidRealTokenReserves)?;
+
+        (
+            bonding_curve.creator,
+            bonding_curve.bump,
+            bonding_curve.real_sol_reserves,
+            token_amount,
+            burn_amount,
+        )
+    };
 
     let seeds_raw: &[&[u8]] = &[
         b"bonding-curve",
         token_mint.as_ref(),
         creator_key.as_ref(),
-        &[bonding_curve.bump],
+        &[bump],
     ];
-    let signer_seeds: &[&[&[u8]]] = &[&seeds_raw[..]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds_raw];
 
-    let expected_relayer_token_ata = spl_associated_token_account::get_associated_token_address(
-        &ctx.accounts.relayer.key(),
-        &ctx.accounts.token_mint.key(),
-    );
-
-    require_keys_eq!(
-        expected_relayer_token_ata,
-        ctx.accounts.relayer_token_account.key(),
-        ErrorCode::InvalidProgramExecutable
-    );
-
-    let token_amount: u64 = bonding_curve.real_token_reserves;
+    if burn_amount > 0 {
+        let cpi_accounts = token::Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::burn(cpi_ctx, burn_amount)?;
+    }
 
     if token_amount > 0 {
         let cpi_accounts = token::Transfer {
             from: ctx.accounts.bonding_curve_token_account.to_account_info(),
-            to: ctx.accounts.relayer_token_account.to_account_info(),
-            authority: bonding_curve.to_account_info(),
+            to: ctx.accounts.migration_vault_token_account.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -50,42 +70,40 @@ pub fn graduate_internal(ctx: Context<BuyTokens>) -> Result<()> {
             signer_seeds,
         );
         token::transfer(cpi_ctx, token_amount)?;
-        bonding_curve.real_token_reserves = 0;
     }
 
-    let expected_relayer_wsol_ata = spl_associated_token_account::get_associated_token_address(
-        &ctx.accounts.relayer.key(),
-        &ctx.accounts.wsol_mint_account.key(),
-    );
-    require_keys_eq!(
-        expected_relayer_wsol_ata,
-        ctx.accounts.relayer_wsol_account.key(),
-        ErrorCode::InvalidProgramExecutable
-    );
-
-    let total_wrap = bonding_curve.real_sol_reserves;
-    if total_wrap > 0 {
-        bonding_curve.sub_lamports(total_wrap)?;
-        ctx.accounts.relayer_wsol_account.add_lamports(total_wrap)?;
-        bonding_curve.real_sol_reserves = 0;
+    if real_sol > 0 {
+        ctx.accounts.bonding_curve.sub_lamports(real_sol)?;
+        ctx.accounts.migration_vault.add_lamports(real_sol)?;
     }
 
-    let actual_token_amount = ctx.accounts.relayer_token_account.amount;
+    let migration_vault = &mut ctx.accounts.migration_vault;
+    migration_vault.token_amount = token_amount;
+    migration_vault.sol_amount = real_sol;
+    migration_vault.deadline = Clock::get()?
+        .unix_timestamp
+        .checked_add(ctx.accounts.global_config.graduation_timeout)
+        .ok_or(PumpError::NumericOverflow)?;
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.token_supply = bonding_curve
+        .token_supply
+        .checked_sub(burn_amount)
+        .ok_or(PumpError::NumericOverflow)?;
+    bonding_curve.real_sol_reserves = 0;
+    bonding_curve.real_token_reserves = 0;
+    bonding_curve.lp_token_reserves = 0;
+    bonding_curve.set_graduation_state(GraduationState::Pending);
 
-    let clock = Clock::get()?;
     emit!(CreatePoolRequestEvent {
-        bonding_curve: bonding_curve.key(),
-        token_mint: ctx.accounts.token_mint.key(),
-        wsol_mint: ctx.accounts.wsol_mint_account.key(),
-        wsol_ata: ctx.accounts.relayer_wsol_account.key(),
-        token_amount: actual_token_amount,
-        token_ata: ctx.accounts.relayer_token_account.key(),
-        wsol_amount: total_wrap,
-        timestamp: clock.unix_timestamp,
-        creator: bonding_curve.creator
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        token_mint,
+        migration_vault: ctx.accounts.migration_vault.key(),
+        token_amount,
+        wsol_amount: real_sol,
+        creator: creator_key,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
-    bonding_curve.graduated = GraduationState::Pending;
-
     Ok(())
 }
@@ -1 +1,2 @@
 pub mod graduate;
+pub mod trade;
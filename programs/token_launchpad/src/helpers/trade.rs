@@ -0,0 +1,305 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token;
+
+use crate::{
+    account::{buy_tokens::BuyTokens, sell_tokens::SellTokens},
+    error::PumpError,
+    helpers::graduate::graduate_internal,
+    math::TradeFees,
+    state::trade::TradeEvent,
+};
+
+/// Moves `sol_amount` (net of fees) into the curve, the platform fee into the
+/// treasury (less any referral share), the creator fee into the creator vault
+/// and `tokens_out` to the buyer, then graduates the curve once the threshold
+/// is hit.
+pub fn execute_buy(
+    ctx: Context<BuyTokens>,
+    sol_amount: u64,
+    mut fees: TradeFees,
+    tokens_out: u64,
+) -> Result<()> {
+    let cfg = &mut ctx.accounts.global_config;
+    if ctx.accounts.referral.is_some() {
+        fees.apply_referral(cfg.referral_fee_share_bps)?;
+    }
+
+    let token_mint = ctx.accounts.token_mint.key();
+    let (creator_key, bump) = {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        (bonding_curve.creator, bonding_curve.bump)
+    };
+
+    let seeds: &[&[u8]] = &[
+        b"bonding-curve",
+        token_mint.as_ref(),
+        creator_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    let transfer_instruction = system_program::Transfer {
+        from: ctx.accounts.buyer.to_account_info(),
+        to: ctx.accounts.bonding_curve.to_account_info(),
+    };
+
+    let cpi_ctx1 = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        transfer_instruction,
+    );
+
+    system_program::transfer(cpi_ctx1, sol_amount)?;
+
+    if fees.platform_fee > 0 {
+        let fee_instruction = system_program::Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+        };
+
+        let fee_cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            fee_instruction,
+        );
+
+        system_program::transfer(fee_cpi_ctx, fees.platform_fee)?;
+    }
+
+    if fees.creator_fee > 0 {
+        let creator_fee_instruction = system_program::Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: ctx.accounts.creator_vault.to_account_info(),
+        };
+
+        let creator_fee_cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            creator_fee_instruction,
+        );
+
+        system_program::transfer(creator_fee_cpi_ctx, fees.creator_fee)?;
+
+        let creator_vault = &mut ctx.accounts.creator_vault;
+        creator_vault.total_accrued = creator_vault
+            .total_accrued
+            .checked_add(fees.creator_fee)
+            .ok_or(PumpError::NumericOverflow)?;
+    }
+
+    let gross_sol_amount = sol_amount
+        .checked_add(fees.total()?)
+        .ok_or(PumpError::NumericOverflow)?;
+
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        if fees.referral_fee > 0 {
+            let referral_fee_instruction = system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: referral.to_account_info(),
+            };
+
+            let referral_fee_cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                referral_fee_instruction,
+            );
+
+            system_program::transfer(referral_fee_cpi_ctx, fees.referral_fee)?;
+        }
+
+        referral.total_earned = referral
+            .total_earned
+            .checked_add(fees.referral_fee)
+            .ok_or(PumpError::NumericOverflow)?;
+        referral.referred_volume_sol = referral
+            .referred_volume_sol
+            .checked_add(gross_sol_amount as u128)
+            .ok_or(PumpError::NumericOverflow)?;
+    }
+
+    let transfer_token_accounts = token::Transfer {
+        from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+        to: ctx.accounts.buyer_token_account.to_account_info(),
+        authority: ctx.accounts.bonding_curve.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_token_accounts,
+        signer_seeds,
+    );
+
+    token::transfer(cpi_ctx, tokens_out)?;
+
+    // The curve's data must not be borrowed across the CPIs above, so its
+    // reserves are only updated once they are done.
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.real_sol_reserves = bonding_curve
+        .real_sol_reserves
+        .checked_add(sol_amount)
+        .ok_or(PumpError::InvalidRealSolReserves)?;
+    bonding_curve.real_token_reserves = bonding_curve
+        .real_token_reserves
+        .checked_sub(tokens_out)
+        .ok_or(PumpError::InvalidRealTokenReserves)?;
+    bonding_curve.virtual_sol_reserves = bonding_curve
+        .virtual_sol_reserves
+        .checked_add(sol_amount)
+        .ok_or(PumpError::InvalidVirtualSolReserves)?;
+    bonding_curve.virtual_token_reserves = bonding_curve
+        .virtual_token_reserves
+        .checked_sub(tokens_out)
+        .ok_or(PumpError::InvalidVirtualTokenReserves)?;
+    cfg.total_volume_sol = cfg
+        .total_volume_sol
+        .checked_add(gross_sol_amount as u128)
+        .ok_or(PumpError::InvalidTotalVolumeSol)?;
+
+    let trader_stats = &mut ctx.accounts.trader_stats;
+    trader_stats.trader = ctx.accounts.buyer.key();
+    trader_stats.bump = ctx.bumps.trader_stats;
+    trader_stats.total_volume_sol = trader_stats
+        .total_volume_sol
+        .checked_add(gross_sol_amount as u128)
+        .ok_or(PumpError::InvalidTotalVolumeSol)?;
+    trader_stats.trade_count = trader_stats
+        .trade_count
+        .checked_add(1)
+        .ok_or(PumpError::NumericOverflow)?;
+
+    emit!(TradeEvent {
+        mint: token_mint,
+        trader: ctx.accounts.buyer.key(),
+        is_buy: true,
+        sol_amount,
+        token_amount: tokens_out,
+        fee: fees.platform_fee,
+        creator_fee: fees.creator_fee,
+        referral_fee: fees.referral_fee,
+        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        virtual_token_reserves: bonding_curve.virtual_token_reserves,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    let graduates = bonding_curve.real_sol_reserves >= cfg.graduation_threshold
+        || bonding_curve.real_token_reserves == 0;
+    drop(bonding_curve);
+
+    if graduates {
+        graduate_internal(ctx)?;
+    }
+
+    Ok(())
+}
+
+/// Takes `tokens_in` from the seller and pays out `sol_out` from the curve, of
+/// which `fees` go to the treasury, creator vault and referrer and the rest to
+/// the seller.
+pub fn execute_sell(
+    ctx: Context<SellTokens>,
+    tokens_in: u64,
+    sol_out: u64,
+    mut fees: TradeFees,
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    let seller = &ctx.accounts.seller;
+
+    if ctx.accounts.referral.is_some() {
+        fees.apply_referral(global_config.referral_fee_share_bps)?;
+    }
+
+    let net_sol_out = sol_out
+        .checked_sub(fees.total()?)
+        .ok_or(PumpError::OverflowOrUnderflowOccurred)?;
+
+    let token_transfer_accounts = token::Transfer {
+        from: ctx.accounts.seller_token_account.to_account_info(),
+        to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+        authority: seller.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token_transfer_accounts,
+    );
+
+    token::transfer(cpi_ctx, tokens_in)?;
+
+    if ctx.accounts.bonding_curve.to_account_info().lamports() < sol_out {
+        return err!(ErrorCode::InvalidProgramExecutable);
+    }
+
+    ctx.accounts.bonding_curve.sub_lamports(sol_out)?;
+    ctx.accounts.seller.add_lamports(net_sol_out)?;
+    if fees.platform_fee > 0 {
+        ctx.accounts.treasury.add_lamports(fees.platform_fee)?;
+    }
+    if fees.creator_fee > 0 {
+        let creator_vault = &mut ctx.accounts.creator_vault;
+        creator_vault.add_lamports(fees.creator_fee)?;
+        creator_vault.total_accrued = creator_vault
+            .total_accrued
+            .checked_add(fees.creator_fee)
+            .ok_or(PumpError::NumericOverflow)?;
+    }
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        if fees.referral_fee > 0 {
+            referral.add_lamports(fees.referral_fee)?;
+        }
+        referral.total_earned = referral
+            .total_earned
+            .checked_add(fees.referral_fee)
+            .ok_or(PumpError::NumericOverflow)?;
+        referral.referred_volume_sol = referral
+            .referred_volume_sol
+            .checked_add(sol_out as u128)
+            .ok_or(PumpError::NumericOverflow)?;
+    }
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.real_token_reserves = bonding_curve
+        .real_token_reserves
+        .checked_add(tokens_in)
+        .ok_or(PumpError::InvalidRealTokenReserves)?;
+    bonding_curve.real_sol_reserves = bonding_curve
+        .real_sol_reserves
+        .checked_sub(sol_out)
+        .ok_or(PumpError::InvalidRealSolReserves)?;
+    bonding_curve.virtual_token_reserves = bonding_curve
+        .virtual_token_reserves
+        .checked_add(tokens_in)
+        .ok_or(PumpError::InvalidVirtualTokenReserves)?;
+    bonding_curve.virtual_sol_reserves = bonding_curve
+        .virtual_sol_reserves
+        .checked_sub(sol_out)
+        .ok_or(PumpError::InvalidVirtualSolReserves)?;
+
+    global_config.total_volume_sol = global_config
+        .total_volume_sol
+        .checked_add(sol_out as u128)
+        .ok_or(ErrorCode::InvalidNumericConversion)?;
+
+    let trader_stats = &mut ctx.accounts.trader_stats;
+    trader_stats.trader = seller.key();
+    trader_stats.bump = ctx.bumps.trader_stats;
+    trader_stats.total_volume_sol = trader_stats
+        .total_volume_sol
+        .checked_add(sol_out as u128)
+        .ok_or(PumpError::InvalidTotalVolumeSol)?;
+    trader_stats.trade_count = trader_stats
+        .trade_count
+        .checked_add(1)
+        .ok_or(PumpError::NumericOverflow)?;
+
+    emit!(TradeEvent {
+        mint: bonding_curve.token_mint,
+        trader: seller.key(),
+        is_buy: false,
+        sol_amount: net_sol_out,
+        token_amount: tokens_in,
+        fee: fees.platform_fee,
+        creator_fee: fees.creator_fee,
+        referral_fee: fees.referral_fee,
+        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        virtual_token_reserves: bonding_curve.virtual_token_reserves,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::{account::global_config::AcceptAuthority, state::config::AuthorityAcceptedEvent};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let cfg = &mut ctx.accounts.global_config;
+
+    let old_authority = cfg.authority;
+    cfg.authority = ctx.accounts.new_authority.key();
+    cfg.pending_authority = None;
+
+    emit!(AuthorityAcceptedEvent {
+        old_authority,
+        new_authority: cfg.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
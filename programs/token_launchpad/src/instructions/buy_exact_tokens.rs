@@ -0,0 +1,46 @@
+use crate::{
+    account::buy_tokens::BuyTokens,
+    error::PumpError,
+    helpers::{graduate::graduate_internal, trade::execute_buy},
+    quote,
+    state::bonding_curve::GraduationState,
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<BuyTokens>, tokens_out: u64, max_sol_in: u64) -> Result<()> {
+    require!(!ctx.accounts.global_config.paused, PumpError::PlatformPaused);
+
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+    require!(!bonding_curve.is_paused(), PumpError::CurvePaused);
+
+    require!(
+        bonding_curve.graduation_state() == GraduationState::Active,
+        PumpError::TokenNotActive
+    );
+    require_gt!(tokens_out, 0, PumpError::InvalidAmount);
+
+    // The threshold can be lowered under a live curve. Nothing more can be
+    // bought then, so the buy graduates the curve instead of failing forever.
+    if bonding_curve.real_sol_reserves >= ctx.accounts.global_config.graduation_threshold {
+        drop(bonding_curve);
+        return graduate_internal(ctx);
+    }
+
+    let curve_snapshot = bonding_curve.snapshot()?;
+    let quote = quote::buy_exact_tokens(
+        &ctx.accounts.global_config.snapshot(ctx.accounts.trader_stats.total_volume_sol),
+        &curve_snapshot,
+        tokens_out,
+    )?;
+    require_gte!(max_sol_in, quote.sol_amount, PumpError::SlippageExceeded);
+
+    let price_impact_bps = quote::buy_price_impact_bps(&curve_snapshot, &quote)?;
+    require_gte!(
+        bonding_curve.price_impact_limit_bps(&ctx.accounts.global_config),
+        price_impact_bps,
+        PumpError::PriceImpactExceeded
+    );
+    drop(bonding_curve);
+
+    execute_buy(ctx, quote.net_sol_amount, quote.fees, quote.tokens_out)
+}
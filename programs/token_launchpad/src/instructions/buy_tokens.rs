@@ -1,109 +1,58 @@
 use crate::{
-    account::buy_tokens::BuyTokens, error::PumpError, helpers::graduate::graduate_internal,
-    math::calculate_tokens_out, state::bonding_curve::GraduationState,
+    account::buy_tokens::BuyTokens,
+    error::PumpError,
+    helpers::{graduate::graduate_internal, trade::execute_buy},
+    quote,
+    state::{bonding_curve::GraduationState, trade::PartialFillEvent},
 };
-use anchor_lang::{prelude::*, system_program};
-use anchor_spl::token;
+use anchor_lang::prelude::*;
 
-pub fn handler(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
-    if ctx.accounts.bonding_curve.graduated == GraduationState::Pending {
-        graduate_internal(ctx)?;
-        return Ok(());
-    }
+pub fn handler(ctx: Context<BuyTokens>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
+    require!(!ctx.accounts.global_config.paused, PumpError::PlatformPaused);
+
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+    require!(!bonding_curve.is_paused(), PumpError::CurvePaused);
 
     require!(
-        ctx.accounts.bonding_curve.graduated == GraduationState::Active,
+        bonding_curve.graduation_state() == GraduationState::Active,
         PumpError::TokenNotActive
     );
+    require_gt!(sol_amount, 0, PumpError::InvalidAmount);
 
-    {
-        let cfg = &mut ctx.accounts.global_config;
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let token_mint = ctx.accounts.token_mint.key();
-        let creator_key = bonding_curve.creator;
-
-        let initial_sol_reserves = bonding_curve.virtual_sol_reserves;
-        let initial_token_reserves = bonding_curve.virtual_token_reserves;
-        let tokens_out =
-            calculate_tokens_out(sol_amount, initial_sol_reserves, initial_token_reserves)?;
-
-        let seeds: &[&[u8]] = &[
-            b"bonding-curve",
-            token_mint.as_ref(),
-            creator_key.as_ref(),
-            &[bonding_curve.bump],
-        ];
-        let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
-
-        let transfer_token_accounts = token::Transfer {
-            from: ctx.accounts.bonding_curve_token_account.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: bonding_curve.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            transfer_token_accounts,
-            signer_seeds,
-        );
-
-        msg!(
-            "bonding_curve lamports before: {}",
-            bonding_curve.to_account_info().lamports()
-        );
-
-        let transfer_instruction = system_program::Transfer {
-            from: ctx.accounts.buyer.to_account_info(),
-            to: bonding_curve.to_account_info(),
-        };
-
-        let cpi_ctx1 = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_instruction,
-        );
-
-        system_program::transfer(cpi_ctx1, sol_amount)?;
-
-        msg!("sol {}", sol_amount);
-
-        bonding_curve.real_sol_reserves = bonding_curve
-            .real_sol_reserves
-            .checked_add(sol_amount)
-            .ok_or(PumpError::InvalidRealSolReserves)?;
-        bonding_curve.real_token_reserves = bonding_curve
-            .real_token_reserves
-            .checked_sub(tokens_out)
-            .ok_or(PumpError::InvalidRealTokenReserves)?;
-        msg!(
-            "Before: virtual_sol_reserves = {}",
-            bonding_curve.virtual_sol_reserves
-        );
-        bonding_curve.virtual_sol_reserves = bonding_curve
-            .virtual_sol_reserves
-            .checked_add(sol_amount)
-            .ok_or(PumpError::InvalidVirtualSolReserves)?;
-        msg!(
-            "After: virtual_sol_reserves = {}",
-            bonding_curve.virtual_sol_reserves
-        );
-        bonding_curve.virtual_token_reserves = bonding_curve
-            .virtual_token_reserves
-            .checked_sub(tokens_out)
-            .ok_or(PumpError::InvalidVirtualTokenReserves)?;
-        cfg.total_volume_sol = cfg
-            .total_volume_sol
-            .checked_add(sol_amount as u128)
-            .ok_or(PumpError::InvalidTotalVolumeSol)?;
-
-        token::transfer(cpi_ctx, tokens_out)?;
+    // The threshold can be lowered under a live curve. Nothing more can be
+    // bought then, so the buy graduates the curve instead of failing forever.
+    if bonding_curve.real_sol_reserves >= ctx.accounts.global_config.graduation_threshold {
+        drop(bonding_curve);
+        return graduate_internal(ctx);
     }
 
-    if ctx.accounts.bonding_curve.real_sol_reserves
-        >= ctx.accounts.global_config.graduation_threshold
-    {
-        msg!("graduated!");
-        graduate_internal(ctx)?;
+    let curve_snapshot = bonding_curve.snapshot()?;
+    let quote = quote::buy(
+        &ctx.accounts.global_config.snapshot(ctx.accounts.trader_stats.total_volume_sol),
+        &curve_snapshot,
+        sol_amount,
+    )?;
+
+    require_gt!(quote.tokens_out, 0, PumpError::ReturnAmountTooSmall);
+    require_gte!(quote.tokens_out, min_tokens_out, PumpError::SlippageExceeded);
+
+    let price_impact_bps = quote::buy_price_impact_bps(&curve_snapshot, &quote)?;
+    require_gte!(
+        bonding_curve.price_impact_limit_bps(&ctx.accounts.global_config),
+        price_impact_bps,
+        PumpError::PriceImpactExceeded
+    );
+    drop(bonding_curve);
+
+    if quote.sol_amount < sol_amount {
+        emit!(PartialFillEvent {
+            mint: ctx.accounts.token_mint.key(),
+            trader: ctx.accounts.buyer.key(),
+            requested_sol_amount: sol_amount,
+            filled_sol_amount: quote.sol_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
     }
 
-    Ok(())
+    execute_buy(ctx, quote.net_sol_amount, quote.fees, quote.tokens_out)
 }
@@ -0,0 +1,33 @@
+use crate::{
+    account::creator_vault::ClaimCreatorFees, error::PumpError,
+    state::creator_vault::CreatorFeesClaimedEvent,
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let creator_vault = &mut ctx.accounts.creator_vault;
+
+    let amount = creator_vault
+        .total_accrued
+        .checked_sub(creator_vault.total_claimed)
+        .ok_or(PumpError::OverflowOrUnderflowOccurred)?;
+    require_gt!(amount, 0, PumpError::InvalidAmount);
+
+    creator_vault.sub_lamports(amount)?;
+    ctx.accounts.creator.add_lamports(amount)?;
+
+    creator_vault.total_claimed = creator_vault
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(PumpError::NumericOverflow)?;
+
+    emit!(CreatorFeesClaimedEvent {
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+        total_claimed: creator_vault.total_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::{
+    account::referral::ClaimReferralRewards, error::PumpError,
+    state::referral::ReferralRewardsClaimedEvent,
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+
+    let amount = referral
+        .total_earned
+        .checked_sub(referral.total_claimed)
+        .ok_or(PumpError::OverflowOrUnderflowOccurred)?;
+    require_gt!(amount, 0, PumpError::InvalidAmount);
+
+    referral.sub_lamports(amount)?;
+    ctx.accounts.referrer.add_lamports(amount)?;
+
+    referral.total_claimed = referral
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(PumpError::NumericOverflow)?;
+
+    emit!(ReferralRewardsClaimedEvent {
+        referrer: referral.referrer,
+        amount,
+        total_claimed: referral.total_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use crate::{
     account::global_config::InitializeGlobalConfig,
     error::PumpError,
-    state::config::{DEFAULT_BUY_FEE_BPS, DEFAULT_CREATION_FEE, DEFAULT_SELL_FEE_BPS},
+    state::config::{
+        DEFAULT_BUY_FEE_BPS, DEFAULT_CREATION_FEE, DEFAULT_CREATOR_FEE_BPS,
+        DEFAULT_GRADUATION_TIMEOUT, DEFAULT_LAUNCH_LIMITS, DEFAULT_MAX_PRICE_IMPACT_BPS,
+        DEFAULT_REFERRAL_FEE_SHARE_BPS, DEFAULT_SELL_FEE_BPS,
+    },
 };
 use anchor_lang::prelude::*;
 
@@ -13,16 +17,24 @@ pub fn handler(
     let cfg = &mut ctx.accounts.global_config;
 
     require!(treasury != Pubkey::default(), PumpError::InvalidTreasury);
+    require!(graduation_threshold > 0, PumpError::IncorrectValue);
 
     cfg.authority = ctx.accounts.admin.key();
+    cfg.pending_authority = None;
     cfg.treasury = treasury;
     cfg.buy_fee_bps = DEFAULT_BUY_FEE_BPS;
     cfg.sell_fee_bps = DEFAULT_SELL_FEE_BPS;
+    cfg.creator_fee_bps = DEFAULT_CREATOR_FEE_BPS;
+    cfg.referral_fee_share_bps = DEFAULT_REFERRAL_FEE_SHARE_BPS;
     cfg.creation_fee = DEFAULT_CREATION_FEE;
     cfg.graduation_threshold = graduation_threshold;
     cfg.total_tokens_created = 0;
     cfg.total_volume_sol = 0;
     cfg.paused = false;
+    cfg.fee_tier_count = 0;
+    cfg.launch_limits = DEFAULT_LAUNCH_LIMITS;
+    cfg.max_price_impact_bps = DEFAULT_MAX_PRICE_IMPACT_BPS;
+    cfg.graduation_timeout = DEFAULT_GRADUATION_TIMEOUT;
     cfg.bump = ctx.bumps.global_config;
 
     Ok(())
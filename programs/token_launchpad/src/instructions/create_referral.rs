@@ -0,0 +1,14 @@
+use crate::account::referral::CreateReferral;
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<CreateReferral>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.referred_volume_sol = 0;
+    referral.total_earned = 0;
+    referral.total_claimed = 0;
+    referral.bump = ctx.bumps.referral;
+
+    Ok(())
+}
@@ -6,41 +6,71 @@ use anchor_spl::metadata::{create_metadata_accounts_v3, CreateMetadataAccountsV3
 use anchor_spl::token;
 use anchor_spl::{metadata::mpl_token_metadata, token::MintTo};
 
-use crate::state::bonding_curve::GraduationState;
-use crate::{
-    account::create_token::CreateToken,
-    state::config::{
-        INITIAL_VIRTUAL_SOL_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES, REAL_TOKEN_RESERVES,
-    },
-};
-
-pub fn handler(ctx: Context<CreateToken>, name: String, symbol: String, uri: String) -> Result<()> {
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
+use crate::state::bonding_curve::{CurveKind, GraduationState, LaunchParams};
+use crate::{account::create_token::CreateToken, error::PumpError};
+
+pub fn handler(
+    ctx: Context<CreateToken>,
+    name: String,
+    symbol: String,
+    uri: String,
+    curve_kind: CurveKind,
+    params: LaunchParams,
+) -> Result<()> {
+    require!(!ctx.accounts.global_config.paused, PumpError::PlatformPaused);
+    ctx.accounts.global_config.launch_limits.check(&params)?;
+    params.check_allocation(curve_kind)?;
+
     let cfg = &mut ctx.accounts.global_config;
     let token_mint = ctx.accounts.token_mint.key();
+    let bump = ctx.bumps.bonding_curve;
 
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_init()?;
     bonding_curve.creator = ctx.accounts.creator.key();
     bonding_curve.token_mint = ctx.accounts.token_mint.key();
     bonding_curve.token_account = ctx.accounts.bonding_curve_token_account.key();
-    bonding_curve.virtual_sol_reserves = INITIAL_VIRTUAL_SOL_RESERVES;
-    bonding_curve.virtual_token_reserves = INITIAL_VIRTUAL_TOKEN_RESERVES;
+    bonding_curve.pool = Pubkey::default();
+    bonding_curve.curve_kind = curve_kind as u8;
+    bonding_curve.token_supply = params.token_supply;
+    bonding_curve.initial_virtual_sol_reserves = params.initial_virtual_sol_reserves;
+    bonding_curve.initial_virtual_token_reserves = params.initial_virtual_token_reserves;
+    bonding_curve.virtual_sol_reserves = params.initial_virtual_sol_reserves;
+    bonding_curve.virtual_token_reserves = params.initial_virtual_token_reserves;
     bonding_curve.real_sol_reserves = 0;
-    bonding_curve.real_token_reserves = REAL_TOKEN_RESERVES;
-    bonding_curve.graduated = GraduationState::Active;
-    bonding_curve.bump = ctx.bumps.bonding_curve;
+    bonding_curve.real_token_reserves = params.curve_supply;
+    bonding_curve.lp_token_reserves = params.lp_supply();
+    bonding_curve.set_graduation_state(GraduationState::Active);
+    bonding_curve.paused = 0;
+    bonding_curve.max_price_impact_bps = 0;
+    bonding_curve.bump = bump;
+    drop(bonding_curve);
+
+    let creator_vault = &mut ctx.accounts.creator_vault;
+    creator_vault.bonding_curve = ctx.accounts.bonding_curve.key();
+    creator_vault.creator = ctx.accounts.creator.key();
+    creator_vault.total_accrued = 0;
+    creator_vault.total_claimed = 0;
+    creator_vault.bump = ctx.bumps.creator_vault;
+
+    let migration_vault = &mut ctx.accounts.migration_vault;
+    migration_vault.bonding_curve = ctx.accounts.bonding_curve.key();
+    migration_vault.token_amount = 0;
+    migration_vault.sol_amount = 0;
+    migration_vault.deadline = 0;
+    migration_vault.bump = ctx.bumps.migration_vault;
 
     let seeds: &[&[u8]] = &[
         b"bonding-curve",
         token_mint.as_ref(),
         ctx.accounts.creator.key.as_ref(),
-        &[bonding_curve.bump],
+        &[bump],
     ];
-    let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
 
     let cpi_accounts = MintTo {
         mint: ctx.accounts.token_mint.to_account_info(),
         to: ctx.accounts.bonding_curve_token_account.to_account_info(),
-        authority: bonding_curve.to_account_info(),
+        authority: ctx.accounts.bonding_curve.to_account_info(),
     };
 
     let cpi_cxt = CpiContext::new_with_signer(
@@ -49,7 +79,7 @@ pub fn handler(ctx: Context<CreateToken>, name: String, symbol: String, uri: Str
         signer_seeds,
     );
 
-    token::mint_to(cpi_cxt, REAL_TOKEN_RESERVES)?;
+    token::mint_to(cpi_cxt, params.token_supply)?;
 
     let (metadata_pda, _metadata_bump) = Pubkey::find_program_address(
         &[
@@ -1,20 +1,123 @@
 use crate::state::graduate::GraduatedEvent;
-use crate::{account::graduate::Graduate, state::bonding_curve::GraduationState};
+use crate::{account::graduate::Graduate, error::PumpError, state::bonding_curve::GraduationState};
+use amm::state::pool::Pool;
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount};
 
-pub fn handler(ctx: Context<Graduate>, pool: Pubkey) -> Result<()> {
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
+/// Releases a `Pending` curve's escrowed reserves into a new AMM pool. The
+/// pool is created through CPI and read back afterwards: it must be an AMM
+/// pool over the launch mint and wSOL whose vaults hold at least what was
+/// migrated before the curve is marked `Graduated`.
+pub fn handler(ctx: Context<Graduate>) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.load()?.graduation_state() == GraduationState::Pending,
+        PumpError::TokenNotActive
+    );
 
-    bonding_curve.graduated = GraduationState::Graduated;
-    bonding_curve.pool = Some(pool);
+    let bonding_curve_key = ctx.accounts.bonding_curve.key();
+    let token_amount = ctx.accounts.migration_vault.token_amount;
+    let sol_amount = ctx.accounts.migration_vault.sol_amount;
+
+    let seeds: &[&[u8]] = &[
+        b"migration-vault",
+        bonding_curve_key.as_ref(),
+        &[ctx.accounts.migration_vault.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    ctx.accounts.migration_vault.sub_lamports(sol_amount)?;
+    ctx.accounts
+        .migration_vault_wsol_account
+        .add_lamports(sol_amount)?;
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token::SyncNative {
+            account: ctx.accounts.migration_vault_wsol_account.to_account_info(),
+        },
+    ))?;
+
+    let cpi_accounts = amm::cpi::accounts::InitializePool {
+        payer: ctx.accounts.payer.to_account_info(),
+        depositor: ctx.accounts.migration_vault.to_account_info(),
+        token_a_mint: ctx.accounts.token_mint.to_account_info(),
+        token_b_mint: ctx.accounts.wsol_mint.to_account_info(),
+        pool: ctx.accounts.pool.to_account_info(),
+        token_a_vault: ctx.accounts.pool_token_vault.to_account_info(),
+        token_b_vault: ctx.accounts.pool_wsol_vault.to_account_info(),
+        depositor_token_a: ctx.accounts.migration_vault_token_account.to_account_info(),
+        depositor_token_b: ctx.accounts.migration_vault_wsol_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.amm_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    amm::cpi::initialize_pool(cpi_ctx, token_amount, sol_amount)?;
+
+    // Defensive only: `pool` is pinned to the AMM's PDA for this mint, wSOL
+    // and migration vault, and the AMM checks its vaults and the deposit
+    // itself, so no accounts a caller can pass reach these errors today.
+    // They guard against the AMM changing underneath this program.
+    let pool = ctx.accounts.pool.key();
+    require_keys_eq!(*ctx.accounts.pool.owner, amm::ID, PumpError::InvalidPool);
+    let pool_account = Pool::try_deserialize(&mut &ctx.accounts.pool.try_borrow_data()?[..])?;
+    require!(
+        pool_account.token_a_mint == ctx.accounts.token_mint.key()
+            && pool_account.token_b_mint == ctx.accounts.wsol_mint.key()
+            && pool_account.token_a_vault == ctx.accounts.pool_token_vault.key()
+            && pool_account.token_b_vault == ctx.accounts.pool_wsol_vault.key()
+            && pool_account.depositor == ctx.accounts.migration_vault.key(),
+        PumpError::InvalidPool
+    );
+    check_pool_reserve(
+        &ctx.accounts.pool_token_vault,
+        pool,
+        ctx.accounts.token_mint.key(),
+        token_amount,
+    )?;
+    check_pool_reserve(
+        &ctx.accounts.pool_wsol_vault,
+        pool,
+        ctx.accounts.wsol_mint.key(),
+        sol_amount,
+    )?;
+
+    let migration_vault = &mut ctx.accounts.migration_vault;
+    migration_vault.token_amount = 0;
+    migration_vault.sol_amount = 0;
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.set_graduation_state(GraduationState::Graduated);
+    bonding_curve.pool = pool;
 
     let ts = Clock::get()?.unix_timestamp;
     emit!(GraduatedEvent {
         mint: bonding_curve.token_mint,
-        authority: bonding_curve.key(),
+        authority: bonding_curve_key,
         timestamp: ts,
         pool
     });
 
     Ok(())
 }
+
+/// Requires `vault` to be a `mint` token account owned by `pool` holding at
+/// least `min_amount`, read back after the AMM has taken the deposit.
+fn check_pool_reserve(
+    vault: &AccountInfo,
+    pool: Pubkey,
+    mint: Pubkey,
+    min_amount: u64,
+) -> Result<()> {
+    require_keys_eq!(*vault.owner, token::ID, PumpError::PoolReservesMismatch);
+    let vault = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?;
+    require!(
+        vault.owner == pool && vault.mint == mint && vault.amount >= min_amount,
+        PumpError::PoolReservesMismatch
+    );
+
+    Ok(())
+}
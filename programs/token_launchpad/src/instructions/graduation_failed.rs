@@ -0,0 +1,114 @@
+use crate::{
+    account::graduation_failed::{GraduationFailed, Redeem},
+    curve::{mul_div, to_u64, Rounding},
+    error::PumpError,
+    state::{
+        bonding_curve::GraduationState,
+        migration_vault::{GraduationFailedEvent, RedeemedEvent},
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+/// Gives up on a curve that is still `Pending` past its deadline. The tokens
+/// escrowed for the pool are burned, so the escrowed SOL is backed only by
+/// the tokens holders still have.
+pub fn handler(ctx: Context<GraduationFailed>) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.load()?.graduation_state() == GraduationState::Pending,
+        PumpError::TokenNotActive
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require_gt!(
+        now,
+        ctx.accounts.migration_vault.deadline,
+        PumpError::GraduationDeadlineNotReached
+    );
+
+    let bonding_curve_key = ctx.accounts.bonding_curve.key();
+    let burned_amount = ctx.accounts.migration_vault.token_amount;
+
+    if burned_amount > 0 {
+        let seeds: &[&[u8]] = &[
+            b"migration-vault",
+            bonding_curve_key.as_ref(),
+            &[ctx.accounts.migration_vault.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = token::Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.migration_vault_token_account.to_account_info(),
+            authority: ctx.accounts.migration_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::burn(cpi_ctx, burned_amount)?;
+    }
+
+    let migration_vault = &mut ctx.accounts.migration_vault;
+    migration_vault.token_amount = 0;
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.token_supply = bonding_curve
+        .token_supply
+        .checked_sub(burned_amount)
+        .ok_or(PumpError::NumericOverflow)?;
+    bonding_curve.set_graduation_state(GraduationState::Failed);
+
+    emit!(GraduationFailedEvent {
+        bonding_curve: bonding_curve_key,
+        token_mint: ctx.accounts.token_mint.key(),
+        sol_amount: migration_vault.sol_amount,
+        burned_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Burns `token_amount` of a failed launch for the holder's pro-rata share of
+/// the escrowed SOL. Shares are taken against the mint's live supply, so
+/// tokens burned outside this program do not leave SOL stranded.
+pub fn redeem_handler(ctx: Context<Redeem>, token_amount: u64) -> Result<()> {
+    require_gt!(token_amount, 0, PumpError::InvalidAmount);
+    require!(
+        ctx.accounts.bonding_curve.load()?.graduation_state() == GraduationState::Failed,
+        PumpError::GraduationNotFailed
+    );
+
+    let sol_amount = to_u64(mul_div(
+        token_amount as u128,
+        ctx.accounts.migration_vault.sol_amount as u128,
+        ctx.accounts.token_mint.supply as u128,
+        Rounding::Down,
+    )?)?;
+
+    let cpi_accounts = token::Burn {
+        mint: ctx.accounts.token_mint.to_account_info(),
+        from: ctx.accounts.holder_token_account.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::burn(cpi_ctx, token_amount)?;
+
+    ctx.accounts.migration_vault.sub_lamports(sol_amount)?;
+    ctx.accounts.holder.add_lamports(sol_amount)?;
+
+    let migration_vault = &mut ctx.accounts.migration_vault;
+    migration_vault.sol_amount -= sol_amount;
+
+    emit!(RedeemedEvent {
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        holder: ctx.accounts.holder.key(),
+        token_amount,
+        sol_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
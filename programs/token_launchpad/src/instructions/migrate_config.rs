@@ -0,0 +1,57 @@
+use crate::{
+    account::global_config::MigrateGlobalConfig,
+    error::PumpError,
+    state::config::{ConfigMigratedEvent, GlobalConfig, GlobalConfigV1},
+};
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+/// Grows a config created before the fields after `bump` were appended and
+/// fills those fields with their defaults. Every other instruction fails to
+/// load such a config until this has run.
+pub fn handler(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+    let info = ctx.accounts.global_config.to_account_info();
+    let old_len = info.data_len();
+    let new_len = 8 + std::mem::size_of::<GlobalConfig>();
+
+    let v1 = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.starts_with(GlobalConfig::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        GlobalConfigV1::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        v1.authority,
+        ctx.accounts.authority.key(),
+        PumpError::NotAuthorized
+    );
+    require_gt!(new_len, old_len, PumpError::ConfigAlreadyMigrated);
+
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if rent_due > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: info.clone(),
+            },
+        );
+        system_program::transfer(cpi_ctx, rent_due)?;
+    }
+    info.resize(new_len)?;
+
+    let cfg = GlobalConfig::from(v1);
+    cfg.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(ConfigMigratedEvent {
+        authority: cfg.authority,
+        old_len: old_len as u64,
+        new_len: new_len as u64,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
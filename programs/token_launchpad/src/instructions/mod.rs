@@ -1,5 +1,21 @@
+pub mod accept_authority;
+pub mod buy_exact_tokens;
 pub mod buy_tokens;
+pub mod claim_creator_fees;
+pub mod claim_referral_rewards;
 pub mod configs;
+pub mod create_referral;
 pub mod create_tokens;
 pub mod graduate;
+pub mod graduation_failed;
+pub mod migrate_config;
+pub mod propose_authority;
+pub mod quote_trade;
+pub mod sell_for_exact_sol;
+pub mod sell_percent;
 pub mod sell_tokens;
+pub mod set_fee_tiers;
+pub mod set_launch_limits;
+pub mod set_paused;
+pub mod set_price_impact;
+pub mod update_config;
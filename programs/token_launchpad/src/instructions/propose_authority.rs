@@ -0,0 +1,24 @@
+use crate::{
+    account::global_config::UpdateGlobalConfig, error::PumpError,
+    state::config::AuthorityProposedEvent,
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<UpdateGlobalConfig>, new_authority: Pubkey) -> Result<()> {
+    let cfg = &mut ctx.accounts.global_config;
+
+    require!(
+        new_authority != Pubkey::default() && new_authority != cfg.authority,
+        PumpError::IncorrectValue
+    );
+
+    cfg.pending_authority = Some(new_authority);
+
+    emit!(AuthorityProposedEvent {
+        authority: cfg.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    account::quote_trade::QuoteTrade,
+    error::PumpError,
+    quote,
+    state::{bonding_curve::GraduationState, trade::TradeQuote},
+};
+
+pub fn buy_handler(ctx: Context<QuoteTrade>, sol_amount: u64) -> Result<TradeQuote> {
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+    require!(
+        bonding_curve.graduation_state() == GraduationState::Active,
+        PumpError::TokenNotActive
+    );
+    let bonding_curve = bonding_curve.snapshot()?;
+
+    let quote = quote::buy(
+        &ctx.accounts.global_config.snapshot(trader_volume_sol(&ctx)),
+        &bonding_curve,
+        sol_amount,
+    )?;
+    Ok(TradeQuote {
+        amount_in: quote.sol_amount,
+        amount_out: quote.tokens_out,
+        fee: quote.fees.total()?,
+        new_price: quote::price_after_buy(&bonding_curve, &quote)?,
+        price_impact_bps: quote::buy_price_impact_bps(&bonding_curve, &quote)?,
+    })
+}
+
+pub fn sell_handler(ctx: Context<QuoteTrade>, tokens_in: u64) -> Result<TradeQuote> {
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+    require!(
+        bonding_curve.graduation_state() == GraduationState::Active,
+        PumpError::TokenNotActive
+    );
+    let bonding_curve = bonding_curve.snapshot()?;
+
+    let quote = quote::sell(
+        &ctx.accounts.global_config.snapshot(trader_volume_sol(&ctx)),
+        &bonding_curve,
+        tokens_in,
+    )?;
+    Ok(TradeQuote {
+        amount_in: quote.tokens_in,
+        amount_out: quote.net_sol_out,
+        fee: quote.fees.total()?,
+        new_price: quote::price_after_sell(&bonding_curve, &quote)?,
+        price_impact_bps: quote::sell_price_impact_bps(&bonding_curve, &quote)?,
+    })
+}
+
+fn trader_volume_sol(ctx: &Context<QuoteTrade>) -> u128 {
+    ctx.accounts
+        .trader_stats
+        .as_ref()
+        .map_or(0, |trader_stats| trader_stats.total_volume_sol)
+}
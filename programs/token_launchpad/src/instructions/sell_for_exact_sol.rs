@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    account::sell_tokens::SellTokens, error::PumpError, helpers::trade::execute_sell, quote,
+    state::bonding_curve::GraduationState,
+};
+
+pub fn handler(ctx: Context<SellTokens>, sol_out: u64, max_tokens_in: u64) -> Result<()> {
+    require!(!ctx.accounts.global_config.paused, PumpError::PlatformPaused);
+
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+    require!(!bonding_curve.is_paused(), PumpError::CurvePaused);
+
+    require!(
+        bonding_curve.graduation_state() == GraduationState::Active,
+        PumpError::TokenNotActive
+    );
+    require_gt!(sol_out, 0, PumpError::InvalidAmount);
+
+    let curve_snapshot = bonding_curve.snapshot()?;
+    let quote = quote::sell_for_exact_sol(
+        &ctx.accounts.global_config.snapshot(ctx.accounts.trader_stats.total_volume_sol),
+        &curve_snapshot,
+        sol_out,
+    )?;
+    require_gte!(max_tokens_in, quote.tokens_in, PumpError::SlippageExceeded);
+
+    let price_impact_bps = quote::sell_price_impact_bps(&curve_snapshot, &quote)?;
+    require_gte!(
+        bonding_curve.price_impact_limit_bps(&ctx.accounts.global_config),
+        price_impact_bps,
+        PumpError::PriceImpactExceeded
+    );
+    drop(bonding_curve);
+
+    execute_sell(ctx, quote.tokens_in, quote.sol_out, quote.fees)
+}
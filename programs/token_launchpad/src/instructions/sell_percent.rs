@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount};
+
+use crate::{
+    account::sell_tokens::SellTokens,
+    curve::{mul_div, to_u64, Rounding},
+    error::PumpError,
+    instructions::sell_tokens,
+    math::BPS_DENOMINATOR,
+};
+
+/// Sells `bps` of the seller's current token balance, closing their token
+/// account when everything is sold.
+pub fn handler(ctx: Context<SellTokens>, bps: u16, min_sol_out: u64) -> Result<()> {
+    require!(
+        bps > 0 && bps as u64 <= BPS_DENOMINATOR,
+        PumpError::IncorrectValue
+    );
+
+    let tokens_in = to_u64(mul_div(
+        ctx.accounts.seller_token_account.amount as u128,
+        bps as u128,
+        BPS_DENOMINATOR as u128,
+        Rounding::Down,
+    )?)?;
+    require_gt!(tokens_in, 0, PumpError::InvalidAmount);
+
+    let close_accounts = (bps as u64 == BPS_DENOMINATOR).then(|| CloseAccount {
+        account: ctx.accounts.seller_token_account.to_account_info(),
+        destination: ctx.accounts.seller.to_account_info(),
+        authority: ctx.accounts.seller.to_account_info(),
+    });
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    sell_tokens::handler(ctx, tokens_in, min_sol_out)?;
+
+    if let Some(close_accounts) = close_accounts {
+        token::close_account(CpiContext::new(token_program, close_accounts))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::{
+    account::global_config::UpdateGlobalConfig,
+    error::PumpError,
+    math::BPS_DENOMINATOR,
+    state::config::{FeeTier, FeeTiersUpdatedEvent, MAX_FEE_TIERS},
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<UpdateGlobalConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    require!(fee_tiers.len() <= MAX_FEE_TIERS, PumpError::IncorrectValue);
+
+    for (i, tier) in fee_tiers.iter().enumerate() {
+        require!(
+            tier.discount_bps as u64 <= BPS_DENOMINATOR,
+            PumpError::IncorrectValue
+        );
+
+        if i > 0 {
+            let previous = &fee_tiers[i - 1];
+            require!(
+                tier.volume_threshold_sol > previous.volume_threshold_sol
+                    && tier.discount_bps >= previous.discount_bps,
+                PumpError::IncorrectValue
+            );
+        }
+    }
+
+    let cfg = &mut ctx.accounts.global_config;
+
+    cfg.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+    cfg.fee_tiers[..fee_tiers.len()].copy_from_slice(&fee_tiers);
+    cfg.fee_tier_count = fee_tiers.len() as u8;
+
+    emit!(FeeTiersUpdatedEvent {
+        fee_tiers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::{
+    account::global_config::UpdateGlobalConfig,
+    error::PumpError,
+    state::config::{LaunchLimits, LaunchLimitsUpdatedEvent},
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<UpdateGlobalConfig>, limits: LaunchLimits) -> Result<()> {
+    require!(limits.is_valid(), PumpError::IncorrectValue);
+
+    let cfg = &mut ctx.accounts.global_config;
+    let old_limits = cfg.launch_limits;
+    cfg.launch_limits = limits;
+
+    emit!(LaunchLimitsUpdatedEvent {
+        old_limits,
+        new_limits: limits,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::{
+    account::global_config::{UpdateBondingCurve, UpdateGlobalConfig},
+    state::config::PauseUpdatedEvent,
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<UpdateGlobalConfig>, paused: bool) -> Result<()> {
+    let cfg = &mut ctx.accounts.global_config;
+
+    cfg.paused = paused;
+
+    emit!(PauseUpdatedEvent {
+        account: cfg.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn curve_handler(ctx: Context<UpdateBondingCurve>, paused: bool) -> Result<()> {
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+
+    bonding_curve.paused = paused as u8;
+
+    emit!(PauseUpdatedEvent {
+        account: ctx.accounts.bonding_curve.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::{
+    account::global_config::UpdateBondingCurve, error::PumpError,
+    state::bonding_curve::CurvePriceImpactUpdatedEvent,
+};
+use anchor_lang::prelude::*;
+
+pub fn curve_handler(
+    ctx: Context<UpdateBondingCurve>,
+    max_price_impact_bps: Option<u16>,
+) -> Result<()> {
+    require!(max_price_impact_bps != Some(0), PumpError::IncorrectValue);
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    let old_max_price_impact_bps = bonding_curve.max_price_impact_bps;
+    bonding_curve.max_price_impact_bps = max_price_impact_bps.unwrap_or(0);
+
+    emit!(CurvePriceImpactUpdatedEvent {
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        old_max_price_impact_bps: Some(old_max_price_impact_bps).filter(|bps| *bps > 0),
+        new_max_price_impact_bps: max_price_impact_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::{
+    account::global_config::UpdateGlobalConfig,
+    error::PumpError,
+    state::config::{
+        ConfigUpdatedEvent, UpdateGlobalConfigParams, MAX_BUY_FEE_BPS, MAX_CREATION_FEE,
+        MAX_CREATOR_FEE_BPS, MAX_REFERRAL_FEE_SHARE_BPS, MAX_SELL_FEE_BPS,
+    },
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<UpdateGlobalConfig>, params: UpdateGlobalConfigParams) -> Result<()> {
+    let cfg = &mut ctx.accounts.global_config;
+
+    let old_treasury = cfg.treasury;
+    let old_buy_fee_bps = cfg.buy_fee_bps;
+    let old_sell_fee_bps = cfg.sell_fee_bps;
+    let old_creator_fee_bps = cfg.creator_fee_bps;
+    let old_referral_fee_share_bps = cfg.referral_fee_share_bps;
+    let old_creation_fee = cfg.creation_fee;
+    let old_graduation_threshold = cfg.graduation_threshold;
+    let old_max_price_impact_bps = cfg.max_price_impact_bps;
+    let old_graduation_timeout = cfg.graduation_timeout;
+
+    if let Some(treasury) = params.treasury {
+        require!(treasury != Pubkey::default(), PumpError::InvalidTreasury);
+        cfg.treasury = treasury;
+    }
+
+    if let Some(buy_fee_bps) = params.buy_fee_bps {
+        require!(buy_fee_bps <= MAX_BUY_FEE_BPS, PumpError::IncorrectValue);
+        cfg.buy_fee_bps = buy_fee_bps;
+    }
+
+    if let Some(sell_fee_bps) = params.sell_fee_bps {
+        require!(sell_fee_bps <= MAX_SELL_FEE_BPS, PumpError::IncorrectValue);
+        cfg.sell_fee_bps = sell_fee_bps;
+    }
+
+    if let Some(creator_fee_bps) = params.creator_fee_bps {
+        require!(
+            creator_fee_bps <= MAX_CREATOR_FEE_BPS,
+            PumpError::IncorrectValue
+        );
+        cfg.creator_fee_bps = creator_fee_bps;
+    }
+
+    if let Some(referral_fee_share_bps) = params.referral_fee_share_bps {
+        require!(
+            referral_fee_share_bps <= MAX_REFERRAL_FEE_SHARE_BPS,
+            PumpError::IncorrectValue
+        );
+        cfg.referral_fee_share_bps = referral_fee_share_bps;
+    }
+
+    if let Some(creation_fee) = params.creation_fee {
+        require!(creation_fee <= MAX_CREATION_FEE, PumpError::IncorrectValue);
+        cfg.creation_fee = creation_fee;
+    }
+
+    if let Some(graduation_threshold) = params.graduation_threshold {
+        require!(graduation_threshold > 0, PumpError::IncorrectValue);
+        cfg.graduation_threshold = graduation_threshold;
+    }
+
+    if let Some(max_price_impact_bps) = params.max_price_impact_bps {
+        require!(max_price_impact_bps > 0, PumpError::IncorrectValue);
+        cfg.max_price_impact_bps = max_price_impact_bps;
+    }
+
+    if let Some(graduation_timeout) = params.graduation_timeout {
+        require!(graduation_timeout > 0, PumpError::IncorrectValue);
+        cfg.graduation_timeout = graduation_timeout;
+    }
+
+    emit!(ConfigUpdatedEvent {
+        authority: cfg.authority,
+        old_treasury,
+        new_treasury: cfg.treasury,
+        old_buy_fee_bps,
+        new_buy_fee_bps: cfg.buy_fee_bps,
+        old_sell_fee_bps,
+        new_sell_fee_bps: cfg.sell_fee_bps,
+        old_creator_fee_bps,
+        new_creator_fee_bps: cfg.creator_fee_bps,
+        old_referral_fee_share_bps,
+        new_referral_fee_share_bps: cfg.referral_fee_share_bps,
+        old_creation_fee,
+        new_creation_fee: cfg.creation_fee,
+        old_graduation_threshold,
+        new_graduation_threshold: cfg.graduation_threshold,
+        old_max_price_impact_bps,
+        new_max_price_impact_bps: cfg.max_price_impact_bps,
+        old_graduation_timeout,
+        new_graduation_timeout: cfg.graduation_timeout,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
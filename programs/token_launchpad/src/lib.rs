@@ -1,18 +1,26 @@
 #![allow(unexpected_cfgs)]
 
 mod account;
-mod state;
+pub mod state;
 use account::buy_tokens::*;
 use account::create_token::*;
+use account::creator_vault::*;
 use account::global_config::*;
 use account::graduate::*;
+use account::graduation_failed::*;
+use account::quote_trade::*;
+use account::referral::*;
 use account::sell_tokens::*;
 mod helpers;
-use account::delete_program_data::*;
 mod instructions;
-mod math;
+pub mod curve;
+pub mod math;
+pub mod quote;
 use anchor_lang::prelude::*;
-mod error;
+use state::bonding_curve::{CurveKind, LaunchParams};
+use state::config::{FeeTier, LaunchLimits, UpdateGlobalConfigParams};
+use state::trade::TradeQuote;
+pub mod error;
 
 declare_id!("HzPQomW78T8mRKMiXaCKEKLgXWvzMxLCRjeffgxfBz3r");
 
@@ -27,8 +35,10 @@ pub mod token_launchpad {
         name: String,
         symbol: String,
         uri: String,
+        curve_kind: CurveKind,
+        params: LaunchParams,
     ) -> Result<()> {
-        instructions::create_tokens::handler(ctx, name, symbol, uri)
+        instructions::create_tokens::handler(ctx, name, symbol, uri, curve_kind, params)
     }
     pub fn init_global_config(
         ctx: Context<InitializeGlobalConfig>,
@@ -38,19 +48,108 @@ pub mod token_launchpad {
         instructions::configs::handler(ctx, treasury, graduation_threshold)
     }
 
-    pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64) -> Result<()> {
-        instructions::buy_tokens::handler(ctx, sol_amount)
+    pub fn migrate_global_config(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
     }
 
-    pub fn sell_tokens(ctx: Context<SellTokens>, tokens_in: u64) -> Result<()> {
-        instructions::sell_tokens::handler(ctx, tokens_in)
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        params: UpdateGlobalConfigParams,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    pub fn propose_authority(
+        ctx: Context<UpdateGlobalConfig>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateGlobalConfig>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    pub fn set_curve_paused(ctx: Context<UpdateBondingCurve>, paused: bool) -> Result<()> {
+        instructions::set_paused::curve_handler(ctx, paused)
+    }
+
+    pub fn set_curve_max_price_impact(
+        ctx: Context<UpdateBondingCurve>,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::set_price_impact::curve_handler(ctx, max_price_impact_bps)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<UpdateGlobalConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::set_fee_tiers::handler(ctx, fee_tiers)
+    }
+
+    pub fn set_launch_limits(ctx: Context<UpdateGlobalConfig>, limits: LaunchLimits) -> Result<()> {
+        instructions::set_launch_limits::handler(ctx, limits)
+    }
+
+    pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
+        instructions::buy_tokens::handler(ctx, sol_amount, min_tokens_out)
+    }
+
+    pub fn sell_tokens(ctx: Context<SellTokens>, tokens_in: u64, min_sol_out: u64) -> Result<()> {
+        instructions::sell_tokens::handler(ctx, tokens_in, min_sol_out)
+    }
+
+    pub fn sell_percent(ctx: Context<SellTokens>, bps: u16, min_sol_out: u64) -> Result<()> {
+        instructions::sell_percent::handler(ctx, bps, min_sol_out)
+    }
+
+    pub fn buy_exact_tokens(
+        ctx: Context<BuyTokens>,
+        tokens_out: u64,
+        max_sol_in: u64,
+    ) -> Result<()> {
+        instructions::buy_exact_tokens::handler(ctx, tokens_out, max_sol_in)
+    }
+
+    pub fn sell_for_exact_sol(
+        ctx: Context<SellTokens>,
+        sol_out: u64,
+        max_tokens_in: u64,
+    ) -> Result<()> {
+        instructions::sell_for_exact_sol::handler(ctx, sol_out, max_tokens_in)
+    }
+
+    pub fn quote_buy(ctx: Context<QuoteTrade>, sol_amount: u64) -> Result<TradeQuote> {
+        instructions::quote_trade::buy_handler(ctx, sol_amount)
+    }
+
+    pub fn quote_sell(ctx: Context<QuoteTrade>, tokens_in: u64) -> Result<TradeQuote> {
+        instructions::quote_trade::sell_handler(ctx, tokens_in)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        instructions::claim_creator_fees::handler(ctx)
+    }
+
+    pub fn create_referral(ctx: Context<CreateReferral>) -> Result<()> {
+        instructions::create_referral::handler(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        instructions::claim_referral_rewards::handler(ctx)
+    }
+
+    pub fn graduate(ctx: Context<Graduate>) -> Result<()> {
+        instructions::graduate::handler(ctx)
     }
 
-    pub fn graduate(ctx: Context<Graduate>, pool: Pubkey) -> Result<()> {
-        instructions::graduate::handler(ctx, pool)
+    pub fn graduation_failed(ctx: Context<GraduationFailed>) -> Result<()> {
+        instructions::graduation_failed::handler(ctx)
     }
 
-    pub fn delete_data(_ctx: Context<DeleteData>) -> Result<()> {
-        Ok(())
+    pub fn redeem(ctx: Context<Redeem>, token_amount: u64) -> Result<()> {
+        instructions::graduation_failed::redeem_handler(ctx, token_amount)
     }
 }
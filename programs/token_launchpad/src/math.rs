@@ -1,97 +1,369 @@
-use crate::error::PumpError;
-use anchor_lang::prelude::*;
-use core::convert::TryFrom;
+use core::{convert::TryFrom, fmt};
 
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Failures of the pricing maths. Kept free of Anchor so off-chain clients can
+/// run the same code; handlers get the matching `PumpError` through `?`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    InsufficientSol,
+    IncorrectValue,
+    OverflowOrUnderflowOccurred,
+    InsufficientTokens,
+    InvalidVirtualSolReserves,
+    InvalidVirtualTokenReserves,
+    InvalidConstant,
+    InvalidTokensCalculation,
+    NumericOverflow,
+    DivideByZero,
+    GraduationThresholdExceeded,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MathError {}
+
+pub type MathResult<T> = Result<T, MathError>;
+
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> MathResult<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(MathError::NumericOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(MathError::DivideByZero)?;
+
+    let fee_u64 = u64::try_from(fee).map_err(|_| MathError::NumericOverflow)?;
+
+    Ok(fee_u64)
+}
+
+#[derive(Clone, Copy)]
+pub struct TradeFees {
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+}
+
+impl TradeFees {
+    pub fn total(&self) -> MathResult<u64> {
+        self.platform_fee
+            .checked_add(self.creator_fee)
+            .and_then(|total| total.checked_add(self.referral_fee))
+            .ok_or(MathError::NumericOverflow)
+    }
+
+    /// Carves `share_bps` of the platform fee out for the referrer.
+    pub fn apply_referral(&mut self, share_bps: u16) -> MathResult<()> {
+        let referral_fee = calculate_fee(self.platform_fee, share_bps)?;
+        self.platform_fee -= referral_fee;
+        self.referral_fee = referral_fee;
+
+        Ok(())
+    }
+}
+
+pub fn calculate_trade_fees(
+    amount: u64,
+    platform_fee_bps: u16,
+    creator_fee_bps: u16,
+) -> MathResult<TradeFees> {
+    Ok(TradeFees {
+        platform_fee: calculate_fee(amount, platform_fee_bps)?,
+        creator_fee: calculate_fee(amount, creator_fee_bps)?,
+        referral_fee: 0,
+    })
+}
+
+/// Splits the gap between `gross_amount` and `net_amount` into platform and
+/// creator fees, with any rounding remainder going to the platform.
+pub fn split_trade_fees(
+    gross_amount: u64,
+    net_amount: u64,
+    creator_fee_bps: u16,
+) -> MathResult<TradeFees> {
+    let total = gross_amount
+        .checked_sub(net_amount)
+        .ok_or(MathError::OverflowOrUnderflowOccurred)?;
+    let creator_fee = calculate_fee(gross_amount, creator_fee_bps)?.min(total);
+
+    Ok(TradeFees {
+        platform_fee: total - creator_fee,
+        creator_fee,
+        referral_fee: 0,
+    })
+}
+
+/// Smallest gross amount that still leaves `net_amount` once `fee_bps` is taken
+/// off, rounded up so the protocol never collects less than its fee.
+pub fn calculate_gross_for_net(net_amount: u64, fee_bps: u16) -> MathResult<u64> {
+    let remaining_bps = (BPS_DENOMINATOR as u128)
+        .checked_sub(fee_bps as u128)
+        .filter(|bps| *bps > 0)
+        .ok_or(MathError::IncorrectValue)?;
+
+    let gross = (net_amount as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(MathError::NumericOverflow)?
+        .checked_add(remaining_bps - 1)
+        .ok_or(MathError::NumericOverflow)?
+        / remaining_bps;
+
+    let gross_u64 = u64::try_from(gross).map_err(|_| MathError::NumericOverflow)?;
+
+    Ok(gross_u64)
+}
+
+/// Tokens released for `sol_amount`, rounded down. The new token reserve is
+/// rounded up so `k` never decreases.
 pub fn calculate_tokens_out(
     sol_amount: u64,
     initial_sol_reserves: u64,
     initial_token_reserves: u64,
-) -> Result<u64> {
+) -> MathResult<u64> {
     let sol_amount_u128 = sol_amount as u128;
     let initial_sol_u128 = initial_sol_reserves as u128;
     let initial_token_u128 = initial_token_reserves as u128;
 
     if initial_sol_u128 == 0 || initial_token_u128 == 0 {
-        return err!(PumpError::InvalidConstant);
+        return Err(MathError::InvalidConstant);
     }
 
     let k = initial_sol_u128
         .checked_mul(initial_token_u128)
-        .ok_or(PumpError::NumericOverflow)?;
+        .ok_or(MathError::NumericOverflow)?;
 
     let new_sol_reserves = initial_sol_u128
         .checked_add(sol_amount_u128)
-        .ok_or(PumpError::NumericOverflow)?;
+        .ok_or(MathError::NumericOverflow)?;
 
     if new_sol_reserves == 0 {
-        return err!(PumpError::DivideByZero);
+        return Err(MathError::DivideByZero);
     }
 
-    let new_token_reserves = k
-        .checked_div(new_sol_reserves)
-        .ok_or(PumpError::InvalidConstant)?;
+    let new_token_reserves = k.div_ceil(new_sol_reserves);
 
     if new_token_reserves > initial_token_u128 {
-        return err!(PumpError::InvalidConstant);
+        return Err(MathError::InvalidConstant);
     }
 
     let tokens_out_u128 = initial_token_u128
         .checked_sub(new_token_reserves)
-        .ok_or(PumpError::InvalidConstant)?;
+        .ok_or(MathError::InvalidConstant)?;
 
-    let tokens_out_u64 = u64::try_from(tokens_out_u128).map_err(|_| PumpError::NumericOverflow)?;
+    let tokens_out_u64 = u64::try_from(tokens_out_u128).map_err(|_| MathError::NumericOverflow)?;
 
     Ok(tokens_out_u64)
 }
 
+/// SOL released for `tokens_in`, rounded down. The new SOL reserve is rounded
+/// up so `k` never decreases.
 pub fn calculate_sol_out(
     tokens_in: u64,
     initial_sol_reserves: u64,
     initial_token_reserves: u64,
-) -> Result<u64> {
-    msg!("calculate_sol_out - tokens_in: {}", tokens_in);
-    msg!(
-        "calculate_sol_out - initial_sol_reserves: {}",
-        initial_sol_reserves
-    );
-    msg!(
-        "calculate_sol_out - initial_token_reserves: {}",
-        initial_token_reserves
-    );
-
+) -> MathResult<u64> {
     let tokens_in_u128 = tokens_in as u128;
     let initial_sol_u128 = initial_sol_reserves as u128;
     let initial_token_u128 = initial_token_reserves as u128;
 
     if initial_sol_u128 == 0 || initial_token_u128 == 0 {
-        return err!(PumpError::InvalidConstant);
+        return Err(MathError::InvalidConstant);
     }
 
     let k = initial_sol_u128
         .checked_mul(initial_token_u128)
-        .ok_or(PumpError::NumericOverflow)?;
+        .ok_or(MathError::NumericOverflow)?;
 
     let new_token_reserves = initial_token_u128
         .checked_add(tokens_in_u128)
-        .ok_or(PumpError::NumericOverflow)?;
+        .ok_or(MathError::NumericOverflow)?;
 
-    let new_sol_reserves = k
-        .checked_div(new_token_reserves)
-        .ok_or(PumpError::InvalidConstant)?;
+    let new_sol_reserves = k.div_ceil(new_token_reserves);
 
     let sol_out_u128 = initial_sol_u128
         .checked_sub(new_sol_reserves)
-        .ok_or(PumpError::InvalidConstant)?;
+        .ok_or(MathError::InvalidConstant)?;
 
-    msg!("calculate_sol_out - k: {}", k);
-    msg!(
-        "calculate_sol_out - new_token_reserves: {}",
-        new_token_reserves
-    );
-    msg!("calculate_sol_out - new_sol_reserves: {}", new_sol_reserves);
-    msg!("calculate_sol_out - sol_out_u128: {}", sol_out_u128);
-
-    let sol_out_u64 = u64::try_from(sol_out_u128).map_err(|_| PumpError::NumericOverflow)?;
+    let sol_out_u64 = u64::try_from(sol_out_u128).map_err(|_| MathError::NumericOverflow)?;
 
     Ok(sol_out_u64)
 }
+
+/// SOL the curve must receive to release exactly `tokens_out`, rounded up.
+pub fn calculate_sol_in(
+    tokens_out: u64,
+    initial_sol_reserves: u64,
+    initial_token_reserves: u64,
+) -> MathResult<u64> {
+    let initial_sol_u128 = initial_sol_reserves as u128;
+    let initial_token_u128 = initial_token_reserves as u128;
+
+    if initial_sol_u128 == 0 || initial_token_u128 == 0 {
+        return Err(MathError::InvalidConstant);
+    }
+
+    let k = initial_sol_u128
+        .checked_mul(initial_token_u128)
+        .ok_or(MathError::NumericOverflow)?;
+
+    let new_token_reserves = initial_token_u128
+        .checked_sub(tokens_out as u128)
+        .filter(|reserves| *reserves > 0)
+        .ok_or(MathError::InsufficientTokens)?;
+
+    let new_sol_reserves = k.div_ceil(new_token_reserves);
+
+    let sol_in_u128 = new_sol_reserves
+        .checked_sub(initial_sol_u128)
+        .ok_or(MathError::InvalidConstant)?;
+
+    let sol_in_u64 = u64::try_from(sol_in_u128).map_err(|_| MathError::NumericOverflow)?;
+
+    Ok(sol_in_u64)
+}
+
+/// Tokens the curve must receive to release exactly `sol_out`, rounded up.
+pub fn calculate_tokens_in(
+    sol_out: u64,
+    initial_sol_reserves: u64,
+    initial_token_reserves: u64,
+) -> MathResult<u64> {
+    let initial_sol_u128 = initial_sol_reserves as u128;
+    let initial_token_u128 = initial_token_reserves as u128;
+
+    if initial_sol_u128 == 0 || initial_token_u128 == 0 {
+        return Err(MathError::InvalidConstant);
+    }
+
+    let k = initial_sol_u128
+        .checked_mul(initial_token_u128)
+        .ok_or(MathError::NumericOverflow)?;
+
+    let new_sol_reserves = initial_sol_u128
+        .checked_sub(sol_out as u128)
+        .filter(|reserves| *reserves > 0)
+        .ok_or(MathError::InsufficientSol)?;
+
+    let new_token_reserves = k.div_ceil(new_sol_reserves);
+
+    let tokens_in_u128 = new_token_reserves
+        .checked_sub(initial_token_u128)
+        .ok_or(MathError::InvalidConstant)?;
+
+    let tokens_in_u64 = u64::try_from(tokens_in_u128).map_err(|_| MathError::NumericOverflow)?;
+
+    Ok(tokens_in_u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn k(sol_reserves: u64, token_reserves: u64) -> u128 {
+        sol_reserves as u128 * token_reserves as u128
+    }
+
+    proptest! {
+        #[test]
+        fn buy_never_decreases_k(sol in 1..=u64::MAX, tokens in 1..=u64::MAX, sol_in: u64) {
+            if let Ok(tokens_out) = calculate_tokens_out(sol_in, sol, tokens) {
+                prop_assert!(tokens_out < tokens);
+                let new_sol = sol as u128 + sol_in as u128;
+                let new_tokens = (tokens - tokens_out) as u128;
+                prop_assert!(new_sol * new_tokens >= k(sol, tokens));
+            }
+        }
+
+        #[test]
+        fn sell_never_decreases_k(sol in 1..=u64::MAX, tokens in 1..=u64::MAX, tokens_in: u64) {
+            if let Ok(sol_out) = calculate_sol_out(tokens_in, sol, tokens) {
+                prop_assert!(sol_out < sol);
+                let new_sol = (sol - sol_out) as u128;
+                let new_tokens = tokens as u128 + tokens_in as u128;
+                prop_assert!(new_sol * new_tokens >= k(sol, tokens));
+            }
+        }
+
+        #[test]
+        fn exact_output_never_decreases_k(
+            sol in 1..=u64::MAX,
+            tokens in 1..=u64::MAX,
+            amount: u64,
+        ) {
+            if let Ok(sol_in) = calculate_sol_in(amount, sol, tokens) {
+                let new_sol = sol as u128 + sol_in as u128;
+                let new_tokens = (tokens - amount) as u128;
+                prop_assert!(new_sol * new_tokens >= k(sol, tokens));
+            }
+            if let Ok(tokens_in) = calculate_tokens_in(amount, sol, tokens) {
+                let new_sol = (sol - amount) as u128;
+                let new_tokens = tokens as u128 + tokens_in as u128;
+                prop_assert!(new_sol * new_tokens >= k(sol, tokens));
+            }
+        }
+
+        #[test]
+        fn buy_then_sell_never_profits(
+            sol in 1..=u64::MAX,
+            tokens in 1..=u64::MAX,
+            sol_in: u64,
+        ) {
+            let Ok(tokens_out) = calculate_tokens_out(sol_in, sol, tokens) else {
+                return Ok(());
+            };
+            let Some(new_sol) = sol.checked_add(sol_in) else {
+                return Ok(());
+            };
+            if let Ok(sol_out) = calculate_sol_out(tokens_out, new_sol, tokens - tokens_out) {
+                prop_assert!(sol_out <= sol_in);
+            }
+        }
+
+        #[test]
+        fn sell_then_buy_never_profits(
+            sol in 1..=u64::MAX,
+            tokens in 1..=u64::MAX,
+            tokens_in: u64,
+        ) {
+            let Ok(sol_out) = calculate_sol_out(tokens_in, sol, tokens) else {
+                return Ok(());
+            };
+            let Some(new_tokens) = tokens.checked_add(tokens_in) else {
+                return Ok(());
+            };
+            if let Ok(tokens_out) = calculate_tokens_out(sol_out, sol - sol_out, new_tokens) {
+                prop_assert!(tokens_out <= tokens_in);
+            }
+        }
+
+        #[test]
+        fn exact_output_costs_at_least_the_forward_quote(
+            sol in 1..=u64::MAX,
+            tokens in 1..=u64::MAX,
+            tokens_out: u64,
+        ) {
+            if let Ok(sol_in) = calculate_sol_in(tokens_out, sol, tokens) {
+                if let Ok(quoted) = calculate_tokens_out(sol_in, sol, tokens) {
+                    prop_assert!(quoted >= tokens_out);
+                }
+            }
+        }
+
+        #[test]
+        fn fees_never_exceed_amount(amount: u64, fee_bps in 0..=BPS_DENOMINATOR as u16) {
+            prop_assert!(calculate_fee(amount, fee_bps)? <= amount);
+        }
+
+        #[test]
+        fn gross_for_net_covers_the_fee(net: u64, fee_bps in 0..BPS_DENOMINATOR as u16) {
+            if let Ok(gross) = calculate_gross_for_net(net, fee_bps) {
+                prop_assert!(gross - calculate_fee(gross, fee_bps)? >= net);
+            }
+        }
+    }
+}
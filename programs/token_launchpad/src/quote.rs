@@ -0,0 +1,423 @@
+//! Pure pricing over plain `CurveSnapshot` and `ConfigSnapshot` values.
+//!
+//! Nothing here logs, reads sysvars or touches Anchor types, so off-chain
+//! clients can link this module and get the exact numbers the trade handlers
+//! execute with. `BondingCurve::snapshot` and `GlobalConfig::snapshot` build
+//! the inputs on-chain.
+
+use crate::{
+    curve::{mul_div, to_u64, AnyCurve, Curve, Rounding, WAD},
+    math::{
+        calculate_gross_for_net, calculate_trade_fees, split_trade_fees, MathError, MathResult,
+        TradeFees, BPS_DENOMINATOR,
+    },
+    state::bonding_curve::CurveKind,
+};
+
+/// The reserves of a bonding curve that pricing depends on.
+#[derive(Clone, Copy)]
+pub struct CurveSnapshot {
+    pub curve_kind: CurveKind,
+    pub token_supply: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+impl CurveSnapshot {
+    pub fn curve(&self) -> AnyCurve {
+        AnyCurve::new(
+            self.curve_kind,
+            self.initial_virtual_sol_reserves,
+            self.initial_virtual_token_reserves,
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+        )
+    }
+}
+
+/// Fee and graduation settings a quote depends on, with the trader's volume
+/// discount already applied to the buy and sell fees.
+#[derive(Clone, Copy)]
+pub struct ConfigSnapshot {
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub graduation_threshold: u64,
+}
+
+#[derive(Clone, Copy)]
+pub struct BuyQuote {
+    /// Lamports the buyer pays, fees included.
+    pub sol_amount: u64,
+    /// Lamports that reach the curve.
+    pub net_sol_amount: u64,
+    pub tokens_out: u64,
+    pub fees: TradeFees,
+}
+
+#[derive(Clone, Copy)]
+pub struct SellQuote {
+    pub tokens_in: u64,
+    /// Lamports that leave the curve, fees included.
+    pub sol_out: u64,
+    /// Lamports the seller receives.
+    pub net_sol_out: u64,
+    pub fees: TradeFees,
+}
+
+/// Marginal price in lamports per base token unit, scaled by `WAD`.
+pub fn spot_price(bonding_curve: &CurveSnapshot) -> MathResult<u128> {
+    bonding_curve.curve().spot_price()
+}
+
+/// Value of the whole token supply at the spot price, in lamports.
+pub fn market_cap(bonding_curve: &CurveSnapshot) -> MathResult<u64> {
+    let market_cap = mul_div(
+        bonding_curve.token_supply as u128,
+        spot_price(bonding_curve)?,
+        WAD,
+        Rounding::Down,
+    )?;
+
+    to_u64(market_cap)
+}
+
+/// How close the curve is to graduating, in basis points. Graduation happens
+/// when either the SOL threshold is reached or the curve tranche sells out.
+pub fn graduation_progress_bps(
+    cfg: &ConfigSnapshot,
+    bonding_curve: &CurveSnapshot,
+) -> MathResult<u16> {
+    let sold = bonding_curve
+        .initial_virtual_token_reserves
+        .saturating_sub(bonding_curve.virtual_token_reserves) as u128;
+    let curve_supply = sold + bonding_curve.real_token_reserves as u128;
+
+    let sol_progress = mul_div(
+        bonding_curve.real_sol_reserves as u128,
+        BPS_DENOMINATOR as u128,
+        cfg.graduation_threshold as u128,
+        Rounding::Down,
+    )?;
+    let token_progress = if curve_supply == 0 {
+        BPS_DENOMINATOR as u128
+    } else {
+        mul_div(sold, BPS_DENOMINATOR as u128, curve_supply, Rounding::Down)?
+    };
+
+    Ok(sol_progress
+        .max(token_progress)
+        .min(BPS_DENOMINATOR as u128) as u16)
+}
+
+/// Spot price once `quote` has been executed against `bonding_curve`.
+pub fn price_after_buy(bonding_curve: &CurveSnapshot, quote: &BuyQuote) -> MathResult<u128> {
+    let mut after = *bonding_curve;
+    after.virtual_sol_reserves = after
+        .virtual_sol_reserves
+        .checked_add(quote.net_sol_amount)
+        .ok_or(MathError::InvalidVirtualSolReserves)?;
+    after.virtual_token_reserves = after
+        .virtual_token_reserves
+        .checked_sub(quote.tokens_out)
+        .ok_or(MathError::InvalidVirtualTokenReserves)?;
+
+    spot_price(&after)
+}
+
+/// Spot price once `quote` has been executed against `bonding_curve`.
+pub fn price_after_sell(bonding_curve: &CurveSnapshot, quote: &SellQuote) -> MathResult<u128> {
+    let mut after = *bonding_curve;
+    after.virtual_sol_reserves = after
+        .virtual_sol_reserves
+        .checked_sub(quote.sol_out)
+        .ok_or(MathError::InvalidVirtualSolReserves)?;
+    after.virtual_token_reserves = after
+        .virtual_token_reserves
+        .checked_add(quote.tokens_in)
+        .ok_or(MathError::InvalidVirtualTokenReserves)?;
+
+    spot_price(&after)
+}
+
+/// Price impact of executing `quote` against `bonding_curve`, in basis points.
+pub fn buy_price_impact_bps(bonding_curve: &CurveSnapshot, quote: &BuyQuote) -> MathResult<u16> {
+    price_impact_bps(
+        spot_price(bonding_curve)?,
+        price_after_buy(bonding_curve, quote)?,
+    )
+}
+
+/// Price impact of executing `quote` against `bonding_curve`, in basis points.
+pub fn sell_price_impact_bps(bonding_curve: &CurveSnapshot, quote: &SellQuote) -> MathResult<u16> {
+    price_impact_bps(
+        spot_price(bonding_curve)?,
+        price_after_sell(bonding_curve, quote)?,
+    )
+}
+
+/// Relative move from `price_before` to `price_after` in basis points,
+/// saturating at `u16::MAX`.
+pub fn price_impact_bps(price_before: u128, price_after: u128) -> MathResult<u16> {
+    let impact = mul_div(
+        price_before.abs_diff(price_after),
+        BPS_DENOMINATOR as u128,
+        price_before,
+        Rounding::Up,
+    )?;
+
+    Ok(impact.min(u16::MAX as u128) as u16)
+}
+
+/// Buy spending up to `sol_amount` lamports, fees included. A buy that would
+/// push the curve past the graduation threshold or sell out the curve tranche
+/// is filled only up to that point, so `sol_amount` on the quote may be less
+/// than requested.
+pub fn buy(
+    cfg: &ConfigSnapshot,
+    bonding_curve: &CurveSnapshot,
+    sol_amount: u64,
+) -> MathResult<BuyQuote> {
+    let fees = calculate_trade_fees(sol_amount, cfg.buy_fee_bps, cfg.creator_fee_bps)?;
+    let net_sol_amount = sol_amount
+        .checked_sub(fees.total()?)
+        .ok_or(MathError::OverflowOrUnderflowOccurred)?;
+
+    let remaining_sol = cfg
+        .graduation_threshold
+        .saturating_sub(bonding_curve.real_sol_reserves);
+    let mut filled_net_sol_amount = net_sol_amount.min(remaining_sol);
+    let mut tokens_out = bonding_curve.curve().tokens_out(filled_net_sol_amount)?;
+
+    if tokens_out > bonding_curve.real_token_reserves {
+        tokens_out = bonding_curve.real_token_reserves;
+        filled_net_sol_amount = bonding_curve.curve().sol_in(tokens_out)?;
+    }
+
+    if filled_net_sol_amount == net_sol_amount {
+        return Ok(BuyQuote {
+            sol_amount,
+            net_sol_amount,
+            tokens_out,
+            fees,
+        });
+    }
+
+    let total_fee_bps = cfg
+        .buy_fee_bps
+        .checked_add(cfg.creator_fee_bps)
+        .ok_or(MathError::NumericOverflow)?;
+    let filled_sol_amount =
+        calculate_gross_for_net(filled_net_sol_amount, total_fee_bps)?.min(sol_amount);
+
+    Ok(BuyQuote {
+        sol_amount: filled_sol_amount,
+        net_sol_amount: filled_net_sol_amount,
+        tokens_out,
+        fees: split_trade_fees(
+            filled_sol_amount,
+            filled_net_sol_amount,
+            cfg.creator_fee_bps,
+        )?,
+    })
+}
+
+/// Buy receiving exactly `tokens_out` tokens. Unlike `buy` this is never
+/// partially filled, so it fails if it would sell more than the curve tranche
+/// or push the curve past the graduation threshold.
+pub fn buy_exact_tokens(
+    cfg: &ConfigSnapshot,
+    bonding_curve: &CurveSnapshot,
+    tokens_out: u64,
+) -> MathResult<BuyQuote> {
+    if tokens_out > bonding_curve.real_token_reserves {
+        return Err(MathError::InsufficientTokens);
+    }
+
+    let net_sol_amount = bonding_curve.curve().sol_in(tokens_out)?;
+    let remaining_sol = cfg
+        .graduation_threshold
+        .saturating_sub(bonding_curve.real_sol_reserves);
+    if net_sol_amount > remaining_sol {
+        return Err(MathError::GraduationThresholdExceeded);
+    }
+    let total_fee_bps = cfg
+        .buy_fee_bps
+        .checked_add(cfg.creator_fee_bps)
+        .ok_or(MathError::NumericOverflow)?;
+    let sol_amount = calculate_gross_for_net(net_sol_amount, total_fee_bps)?;
+    let fees = split_trade_fees(sol_amount, net_sol_amount, cfg.creator_fee_bps)?;
+
+    Ok(BuyQuote {
+        sol_amount,
+        net_sol_amount,
+        tokens_out,
+        fees,
+    })
+}
+
+/// Sell of exactly `tokens_in` tokens.
+pub fn sell(
+    cfg: &ConfigSnapshot,
+    bonding_curve: &CurveSnapshot,
+    tokens_in: u64,
+) -> MathResult<SellQuote> {
+    let sol_out = bonding_curve.curve().sol_out(tokens_in)?;
+    let fees = calculate_trade_fees(sol_out, cfg.sell_fee_bps, cfg.creator_fee_bps)?;
+    let net_sol_out = sol_out
+        .checked_sub(fees.total()?)
+        .ok_or(MathError::OverflowOrUnderflowOccurred)?;
+
+    Ok(SellQuote {
+        tokens_in,
+        sol_out,
+        net_sol_out,
+        fees,
+    })
+}
+
+/// Sell paying the seller exactly `net_sol_out` lamports after fees.
+pub fn sell_for_exact_sol(
+    cfg: &ConfigSnapshot,
+    bonding_curve: &CurveSnapshot,
+    net_sol_out: u64,
+) -> MathResult<SellQuote> {
+    let total_fee_bps = cfg
+        .sell_fee_bps
+        .checked_add(cfg.creator_fee_bps)
+        .ok_or(MathError::NumericOverflow)?;
+    let sol_out = calculate_gross_for_net(net_sol_out, total_fee_bps)?;
+    if sol_out > bonding_curve.real_sol_reserves {
+        return Err(MathError::InsufficientSol);
+    }
+
+    let tokens_in = bonding_curve.curve().tokens_in(sol_out)?;
+    let fees = split_trade_fees(sol_out, net_sol_out, cfg.creator_fee_bps)?;
+
+    Ok(SellQuote {
+        tokens_in,
+        sol_out,
+        net_sol_out,
+        fees,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn global_config() -> ConfigSnapshot {
+        ConfigSnapshot {
+            buy_fee_bps: 100,
+            sell_fee_bps: 100,
+            creator_fee_bps: 50,
+            graduation_threshold: 85_000_000_000,
+        }
+    }
+
+    fn bonding_curve() -> CurveSnapshot {
+        CurveSnapshot {
+            curve_kind: CurveKind::ConstantProduct,
+            token_supply: 1_000_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 793_100_000_000_000,
+        }
+    }
+
+    #[test]
+    fn buy_quote_splits_fees_off_the_gross_amount() {
+        let quote = buy(&global_config(), &bonding_curve(), 1_000_000_000).unwrap();
+
+        assert_eq!(quote.fees.platform_fee, 10_000_000);
+        assert_eq!(quote.fees.creator_fee, 5_000_000);
+        assert_eq!(quote.net_sol_amount, 985_000_000);
+        assert!(quote.tokens_out > 0);
+    }
+
+    #[test]
+    fn buy_quote_stops_at_the_graduation_threshold() {
+        let cfg = global_config();
+        let mut curve = bonding_curve();
+        curve.real_sol_reserves = cfg.graduation_threshold - 1_000_000_000;
+
+        let quote = buy(&cfg, &curve, 10_000_000_000).unwrap();
+
+        assert_eq!(quote.net_sol_amount, 1_000_000_000);
+        assert!(quote.sol_amount < 10_000_000_000);
+        assert_eq!(
+            quote.sol_amount - quote.fees.total().unwrap(),
+            quote.net_sol_amount
+        );
+    }
+
+    #[test]
+    fn buy_quote_stops_when_the_curve_tranche_sells_out() {
+        let mut cfg = global_config();
+        cfg.graduation_threshold = u64::MAX;
+        let curve = bonding_curve();
+
+        let quote = buy(&cfg, &curve, 1_000_000_000_000).unwrap();
+
+        assert_eq!(quote.tokens_out, curve.real_token_reserves);
+        assert!(quote.sol_amount < 1_000_000_000_000);
+    }
+
+    #[test]
+    fn exact_output_quotes_agree_with_forward_quotes() {
+        let cfg = global_config();
+        let curve = bonding_curve();
+
+        let exact = buy_exact_tokens(&cfg, &curve, 1_000_000_000_000).unwrap();
+        let forward = buy(&cfg, &curve, exact.sol_amount).unwrap();
+
+        assert!(forward.tokens_out >= exact.tokens_out);
+    }
+
+    #[test]
+    fn exact_output_quotes_stop_at_the_graduation_threshold() {
+        let cfg = global_config();
+        let mut curve = bonding_curve();
+        curve.real_sol_reserves = cfg.graduation_threshold - 1_000_000_000;
+
+        let within = buy(&cfg, &curve, 2_000_000_000).unwrap();
+        assert_eq!(within.net_sol_amount, 1_000_000_000);
+        assert!(buy_exact_tokens(&cfg, &curve, within.tokens_out).is_ok());
+
+        assert_eq!(
+            buy_exact_tokens(&cfg, &curve, within.tokens_out * 2).err(),
+            Some(MathError::GraduationThresholdExceeded)
+        );
+        assert_eq!(
+            buy_exact_tokens(&cfg, &curve, curve.real_token_reserves + 1).err(),
+            Some(MathError::InsufficientTokens)
+        );
+    }
+
+    #[test]
+    fn market_cap_is_supply_at_spot_price() {
+        // 30 SOL / 1.073e15 units priced across 1e15 units.
+        assert_eq!(market_cap(&bonding_curve()).unwrap(), 27_958_993_476);
+    }
+
+    #[test]
+    fn graduation_progress_tracks_the_closer_trigger() {
+        let cfg = global_config();
+        let mut curve = bonding_curve();
+        assert_eq!(graduation_progress_bps(&cfg, &curve).unwrap(), 0);
+
+        curve.real_sol_reserves = cfg.graduation_threshold / 2;
+        assert_eq!(graduation_progress_bps(&cfg, &curve).unwrap(), 5_000);
+
+        curve.virtual_token_reserves -= curve.real_token_reserves;
+        curve.real_token_reserves = 0;
+        assert_eq!(graduation_progress_bps(&cfg, &curve).unwrap(), 10_000);
+    }
+}
@@ -1,23 +1,352 @@
 use anchor_lang::prelude::*;
 
+use crate::curve::{mul_div, to_u64, AnyCurve, Curve, Rounding, WAD};
+use crate::error::PumpError;
+use crate::quote::CurveSnapshot;
+use crate::state::config::GlobalConfig;
+
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum GraduationState {
     Active,
     Pending,
     Graduated,
+    /// Missed its graduation deadline; holders redeem for the escrowed SOL.
+    Failed,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CurveKind {
+    ConstantProduct,
+    Linear,
+    Exponential,
+}
+
+/// Creator-chosen shape of a launch, bounded by `GlobalConfig::launch_limits`.
+///
+/// `token_supply` is split into a `curve_supply` tranche sold on the curve and
+/// an LP tranche holding the rest, which is migrated alongside the raised SOL
+/// at graduation.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct LaunchParams {
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub token_supply: u64,
+    pub curve_supply: u64,
+    pub decimals: u8,
+}
+
+impl LaunchParams {
+    pub fn lp_supply(&self) -> u64 {
+        self.token_supply.saturating_sub(self.curve_supply)
+    }
+
+    /// Checks that the LP tranche can be paired with everything the curve
+    /// raises by the time `curve_supply` sells out, at the curve's final price.
+    pub fn check_allocation(&self, curve_kind: CurveKind) -> Result<()> {
+        require!(
+            self.curve_supply > 0
+                && self.curve_supply < self.token_supply
+                && self.curve_supply < self.initial_virtual_token_reserves,
+            PumpError::InvalidSupplyAllocation
+        );
+
+        let start = AnyCurve::new(
+            curve_kind,
+            self.initial_virtual_sol_reserves,
+            self.initial_virtual_token_reserves,
+            self.initial_virtual_sol_reserves,
+            self.initial_virtual_token_reserves,
+        );
+        let raised = start.sol_in(self.curve_supply)?;
+
+        let end = AnyCurve::new(
+            curve_kind,
+            self.initial_virtual_sol_reserves,
+            self.initial_virtual_token_reserves,
+            self.initial_virtual_sol_reserves
+                .checked_add(raised)
+                .ok_or(PumpError::NumericOverflow)?,
+            self.initial_virtual_token_reserves - self.curve_supply,
+        );
+        let lp_needed = mul_div(raised as u128, WAD, end.spot_price()?, Rounding::Up)?;
+
+        require!(
+            self.lp_supply() as u128 >= lp_needed,
+            PumpError::InvalidSupplyAllocation
+        );
+
+        Ok(())
+    }
 }
 
-#[account]
+/// Zero-copy so trades read and write reserves in place instead of
+/// round-tripping the whole account through Borsh. Enums and flags are stored
+/// as `u8` and exposed through accessors.
+///
+/// Curves created with the original Borsh layout cannot be loaded and are not
+/// migrated: they were sized from hard-coded reserves that no `LaunchParams`
+/// reproduces. A deployment with live curves needs a fresh program instead
+/// of an in-place upgrade.
+#[account(zero_copy)]
 pub struct BondingCurve {
     pub creator: Pubkey,
     pub token_mint: Pubkey,
     pub token_account: Pubkey,
-    pub pool: Option<Pubkey>,
+    /// `Pubkey::default()` until the curve graduates into a pool.
+    pub pool: Pubkey,
+    pub token_supply: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_virtual_token_reserves: u64,
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
-    pub graduated: GraduationState,
+    pub lp_token_reserves: u64,
+    /// Overrides `GlobalConfig::max_price_impact_bps` for this curve when
+    /// non-zero.
+    pub max_price_impact_bps: u16,
+    pub curve_kind: u8,
+    pub graduated: u8,
+    pub paused: u8,
     pub bump: u8,
+    pub _padding: [u8; 2],
+}
+
+impl BondingCurve {
+    pub fn curve_kind(&self) -> Result<CurveKind> {
+        match self.curve_kind {
+            0 => Ok(CurveKind::ConstantProduct),
+            1 => Ok(CurveKind::Linear),
+            2 => Ok(CurveKind::Exponential),
+            _ => err!(PumpError::InvalidCurveKind),
+        }
+    }
+
+    pub fn graduation_state(&self) -> GraduationState {
+        match self.graduated {
+            0 => GraduationState::Active,
+            1 => GraduationState::Pending,
+            2 => GraduationState::Graduated,
+            _ => GraduationState::Failed,
+        }
+    }
+
+    pub fn set_graduation_state(&mut self, state: GraduationState) {
+        self.graduated = state as u8;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    pub fn snapshot(&self) -> Result<CurveSnapshot> {
+        Ok(CurveSnapshot {
+            curve_kind: self.curve_kind()?,
+            token_supply: self.token_supply,
+            initial_virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            initial_virtual_token_reserves: self.initial_virtual_token_reserves,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+        })
+    }
+
+    pub fn curve(&self) -> Result<AnyCurve> {
+        Ok(self.snapshot()?.curve())
+    }
+
+    pub fn price_impact_limit_bps(&self, cfg: &GlobalConfig) -> u16 {
+        match self.max_price_impact_bps {
+            0 => cfg.max_price_impact_bps,
+            max_price_impact_bps => max_price_impact_bps,
+        }
+    }
+
+    /// Tokens to pair with `sol_amount` lamports so the pool opens at the
+    /// curve's current price, capped at what the curve still holds.
+    pub fn lp_tokens_for(&self, sol_amount: u64) -> Result<u64> {
+        let available = self
+            .lp_token_reserves
+            .checked_add(self.real_token_reserves)
+            .ok_or(PumpError::NumericOverflow)?;
+        let matched = mul_div(
+            sol_amount as u128,
+            WAD,
+            self.curve()?.spot_price()?,
+            Rounding::Down,
+        )?;
+
+        Ok(to_u64(matched.min(available as u128))?)
+    }
+}
+
+#[event]
+pub struct CurvePriceImpactUpdatedEvent {
+    pub bonding_curve: Pubkey,
+    pub old_max_price_impact_bps: Option<u16>,
+    pub new_max_price_impact_bps: Option<u16>,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A fresh launch of the given curve kind after an opening buy of `opening_sol`,
+    /// applied the way `execute_buy` updates the reserves.
+    fn launch(
+        curve_kind: u8,
+        virtual_sol: u64,
+        virtual_tokens: u64,
+        opening_sol: u64,
+    ) -> Option<BondingCurve> {
+        let mut bonding_curve = BondingCurve {
+            initial_virtual_sol_reserves: virtual_sol,
+            initial_virtual_token_reserves: virtual_tokens,
+            virtual_sol_reserves: virtual_sol,
+            virtual_token_reserves: virtual_tokens,
+            curve_kind,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        let tokens_out = bonding_curve.curve().ok()?.tokens_out(opening_sol).ok()?;
+        apply_buy(&mut bonding_curve, opening_sol, tokens_out)?;
+
+        Some(bonding_curve)
+    }
+
+    fn apply_buy(bonding_curve: &mut BondingCurve, sol_in: u64, tokens_out: u64) -> Option<()> {
+        bonding_curve.virtual_sol_reserves =
+            bonding_curve.virtual_sol_reserves.checked_add(sol_in)?;
+        bonding_curve.virtual_token_reserves = bonding_curve
+            .virtual_token_reserves
+            .checked_sub(tokens_out)?;
+        Some(())
+    }
+
+    fn sold(bonding_curve: &BondingCurve) -> u64 {
+        bonding_curve.initial_virtual_token_reserves - bonding_curve.virtual_token_reserves
+    }
+
+    fn held(bonding_curve: &BondingCurve) -> u64 {
+        bonding_curve.virtual_sol_reserves - bonding_curve.initial_virtual_sol_reserves
+    }
+
+    fn bps_of(amount: u64, bps: u64) -> u64 {
+        (amount as u128 * bps as u128 / 10_000) as u64
+    }
+
+    /// Lamports the curve would owe if every sold token came back, which must
+    /// never exceed what it has taken in. For the constant-product curve this
+    /// is the same as `k` never decreasing.
+    fn assert_solvent(bonding_curve: &BondingCurve) -> std::result::Result<(), TestCaseError> {
+        if let Ok(owed) = bonding_curve.curve().unwrap().sol_out(sold(bonding_curve)) {
+            let held = held(bonding_curve);
+            prop_assert!(owed <= held, "owes {} but holds {}", owed, held);
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn trades_never_leave_the_curve_short(
+            kind in CurveKind::ConstantProduct as u8..=CurveKind::Exponential as u8,
+            sol in 1_000_000_000..=100_000_000_000_000u64,
+            tokens in 1_000_000_000_000..=10_000_000_000_000_000_000u64,
+            opening in 0..=1_000_000_000_000u64,
+            sol_in in 0..=1_000_000_000_000u64,
+            sell_bps in 0..=10_000u64,
+        ) {
+            let Some(mut bonding_curve) = launch(kind, sol, tokens, opening) else {
+                return Ok(());
+            };
+            assert_solvent(&bonding_curve)?;
+
+            if let Ok(tokens_out) = bonding_curve.curve()?.tokens_out(sol_in) {
+                if apply_buy(&mut bonding_curve, sol_in, tokens_out).is_some() {
+                    assert_solvent(&bonding_curve)?;
+                }
+            }
+
+            let tokens_in = bps_of(sold(&bonding_curve), sell_bps);
+            if let Ok(sol_out) = bonding_curve.curve()?.sol_out(tokens_in) {
+                bonding_curve.virtual_sol_reserves -= sol_out;
+                bonding_curve.virtual_token_reserves += tokens_in;
+                assert_solvent(&bonding_curve)?;
+            }
+        }
+
+        #[test]
+        fn buy_then_sell_never_profits(
+            kind in CurveKind::ConstantProduct as u8..=CurveKind::Exponential as u8,
+            sol in 1_000_000_000..=100_000_000_000_000u64,
+            tokens in 1_000_000_000_000..=10_000_000_000_000_000_000u64,
+            opening in 0..=1_000_000_000_000u64,
+            sol_in in 0..=1_000_000_000_000u64,
+        ) {
+            let Some(mut bonding_curve) = launch(kind, sol, tokens, opening) else {
+                return Ok(());
+            };
+            let Ok(tokens_out) = bonding_curve.curve()?.tokens_out(sol_in) else {
+                return Ok(());
+            };
+            if apply_buy(&mut bonding_curve, sol_in, tokens_out).is_none() {
+                return Ok(());
+            }
+            if let Ok(sol_out) = bonding_curve.curve()?.sol_out(tokens_out) {
+                prop_assert!(sol_out <= sol_in);
+            }
+        }
+
+        #[test]
+        fn exact_output_costs_at_least_the_forward_quote(
+            kind in CurveKind::ConstantProduct as u8..=CurveKind::Exponential as u8,
+            sol in 1_000_000_000..=100_000_000_000_000u64,
+            tokens in 1_000_000_000_000..=10_000_000_000_000_000_000u64,
+            opening in 0..=1_000_000_000_000u64,
+            tokens_bps in 0..=10_000u64,
+            sol_bps in 0..=10_000u64,
+        ) {
+            let Some(bonding_curve) = launch(kind, sol, tokens, opening) else {
+                return Ok(());
+            };
+            let curve = bonding_curve.curve()?;
+
+            let tokens_out = bps_of(bonding_curve.virtual_token_reserves, tokens_bps);
+            if let Ok(sol_in) = curve.sol_in(tokens_out) {
+                if let Ok(quoted) = curve.tokens_out(sol_in) {
+                    prop_assert!(quoted >= tokens_out);
+                }
+            }
+
+            let sol_out = bps_of(held(&bonding_curve), sol_bps);
+            if let Ok(tokens_in) = curve.tokens_in(sol_out) {
+                prop_assert!(curve.sol_out(tokens_in)? >= sol_out);
+            }
+        }
+
+        #[test]
+        fn outputs_never_exceed_reserves(
+            kind in CurveKind::ConstantProduct as u8..=CurveKind::Exponential as u8,
+            sol in 1_000_000_000..=100_000_000_000_000u64,
+            tokens in 1_000_000_000_000..=10_000_000_000_000_000_000u64,
+            opening in 0..=1_000_000_000_000u64,
+            amount: u64,
+        ) {
+            let Some(bonding_curve) = launch(kind, sol, tokens, opening) else {
+                return Ok(());
+            };
+            let curve = bonding_curve.curve()?;
+
+            if let Ok(tokens_out) = curve.tokens_out(amount) {
+                prop_assert!(tokens_out <= bonding_curve.virtual_token_reserves);
+            }
+            if let Ok(sol_out) = curve.sol_out(amount) {
+                prop_assert!(sol_out <= bonding_curve.virtual_sol_reserves);
+            }
+        }
+    }
 }
@@ -1,22 +1,99 @@
 use anchor_lang::prelude::*;
 
-pub const INITIAL_VIRTUAL_SOL_RESERVES: u64 = 1_000_000_000_000; // 1000 SOL in lamports
-pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_000_000_000_000_000; // 1 billion tokens with 6 decimals (1_000_000_000 * 10^6)
-pub const REAL_TOKEN_RESERVES: u64 = 1_000_000_000_000_000_000; // 1 trillion tokens with 6 decimals
+use crate::error::PumpError;
+use crate::math::BPS_DENOMINATOR;
+use crate::quote::ConfigSnapshot;
+use crate::state::bonding_curve::LaunchParams;
 
-pub const TOKEN_DECIMALS: u8 = 6;
+pub const MAX_TOKEN_DECIMALS: u8 = 9;
+
+pub const DEFAULT_LAUNCH_LIMITS: LaunchLimits = LaunchLimits {
+    min_virtual_sol_reserves: 1_000_000_000, // 1 SOL in lamports
+    max_virtual_sol_reserves: 10_000_000_000_000, // 10_000 SOL in lamports
+    min_virtual_token_reserves: 1_000_000_000_000,
+    max_virtual_token_reserves: 10_000_000_000_000_000_000,
+    min_token_supply: 1_000_000_000_000,
+    max_token_supply: 10_000_000_000_000_000_000,
+    min_decimals: 0,
+    max_decimals: MAX_TOKEN_DECIMALS,
+};
 
 pub const DEFAULT_BUY_FEE_BPS: u16 = 100;
 pub const DEFAULT_SELL_FEE_BPS: u16 = 100;
+pub const DEFAULT_CREATOR_FEE_BPS: u16 = 50;
+pub const DEFAULT_REFERRAL_FEE_SHARE_BPS: u16 = 1000;
 pub const DEFAULT_CREATION_FEE: u64 = 20_000_000;
 pub const DEFAULT_GRADUATION_THRESHOLD: u64 = 85_000_000_000;
+/// Price impact saturates at `u16::MAX`, so the default never rejects a trade.
+pub const DEFAULT_MAX_PRICE_IMPACT_BPS: u16 = u16::MAX;
+pub const DEFAULT_GRADUATION_TIMEOUT: i64 = 3 * 24 * 60 * 60; // 3 days in seconds
+
+pub const MAX_BUY_FEE_BPS: u16 = 1000;
+pub const MAX_SELL_FEE_BPS: u16 = 1000;
+pub const MAX_CREATOR_FEE_BPS: u16 = 500;
+pub const MAX_REFERRAL_FEE_SHARE_BPS: u16 = 5000;
+pub const MAX_CREATION_FEE: u64 = 100_000_000;
 
-// pub const MAX_BUY_FEE_BPS: u16 = 1000;
-// pub const MAX_SELL_FEE_BPS: u16 = 1000;
-// pub const MAX_CREATION_FEE: u64 = 100_000_000;
+pub const MAX_FEE_TIERS: usize = 4;
 
 #[account]
 pub struct GlobalConfig {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    pub creation_fee: u64,
+    pub graduation_threshold: u64,
+    pub total_tokens_created: u64,
+    pub total_volume_sol: u128,
+    /// Held the relayer `graduate` used to require. Unused since graduation
+    /// became permissionless; reserved so the fields after it keep their
+    /// offsets.
+    pub _reserved: [u8; 32],
+    pub paused: bool,
+    pub bump: u8,
+    // Fields below were added after the first deployment. New fields go at
+    // the end so the ones above keep their offsets, but a config created
+    // before them is too small to load until `migrate_global_config` grows it.
+    pub pending_authority: Option<Pubkey>,
+    pub creator_fee_bps: u16,
+    pub referral_fee_share_bps: u16,
+    pub fee_tier_count: u8,
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    pub launch_limits: LaunchLimits,
+    pub max_price_impact_bps: u16,
+    pub graduation_timeout: i64,
+}
+
+impl GlobalConfig {
+    /// Applies the discount of the highest fee tier whose threshold
+    /// `trader_volume_sol` has reached.
+    pub fn discounted_fee_bps(&self, fee_bps: u16, trader_volume_sol: u128) -> u16 {
+        let discount_bps = self.fee_tiers[..self.fee_tier_count as usize]
+            .iter()
+            .rev()
+            .find(|tier| trader_volume_sol >= tier.volume_threshold_sol as u128)
+            .map_or(0, |tier| tier.discount_bps);
+
+        (fee_bps as u32 * (BPS_DENOMINATOR as u32 - discount_bps as u32) / BPS_DENOMINATOR as u32)
+            as u16
+    }
+
+    /// The settings a quote for a trader with `trader_volume_sol` of volume
+    /// depends on.
+    pub fn snapshot(&self, trader_volume_sol: u128) -> ConfigSnapshot {
+        ConfigSnapshot {
+            buy_fee_bps: self.discounted_fee_bps(self.buy_fee_bps, trader_volume_sol),
+            sell_fee_bps: self.discounted_fee_bps(self.sell_fee_bps, trader_volume_sol),
+            creator_fee_bps: self.creator_fee_bps,
+            graduation_threshold: self.graduation_threshold,
+        }
+    }
+}
+
+/// `GlobalConfig` as first deployed. Only read by `migrate_global_config`.
+#[derive(AnchorDeserialize)]
+pub struct GlobalConfigV1 {
     pub authority: Pubkey,
     pub treasury: Pubkey,
     pub buy_fee_bps: u16,
@@ -29,3 +106,210 @@ pub struct GlobalConfig {
     pub paused: bool,
     pub bump: u8,
 }
+
+impl From<GlobalConfigV1> for GlobalConfig {
+    /// Keeps every original setting and gives the appended fields the
+    /// defaults a fresh config starts with.
+    fn from(v1: GlobalConfigV1) -> Self {
+        Self {
+            authority: v1.authority,
+            treasury: v1.treasury,
+            buy_fee_bps: v1.buy_fee_bps,
+            sell_fee_bps: v1.sell_fee_bps,
+            creation_fee: v1.creation_fee,
+            graduation_threshold: v1.graduation_threshold,
+            total_tokens_created: v1.total_tokens_created,
+            total_volume_sol: v1.total_volume_sol,
+            _reserved: [0; 32],
+            paused: v1.paused,
+            bump: v1.bump,
+            pending_authority: None,
+            creator_fee_bps: DEFAULT_CREATOR_FEE_BPS,
+            referral_fee_share_bps: DEFAULT_REFERRAL_FEE_SHARE_BPS,
+            fee_tier_count: 0,
+            fee_tiers: [FeeTier::default(); MAX_FEE_TIERS],
+            launch_limits: DEFAULT_LAUNCH_LIMITS,
+            max_price_impact_bps: DEFAULT_MAX_PRICE_IMPACT_BPS,
+            graduation_timeout: DEFAULT_GRADUATION_TIMEOUT,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub volume_threshold_sol: u64,
+    pub discount_bps: u16,
+}
+
+/// Admin-configured inclusive ranges that creator-supplied `LaunchParams`
+/// must fall within.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LaunchLimits {
+    pub min_virtual_sol_reserves: u64,
+    pub max_virtual_sol_reserves: u64,
+    pub min_virtual_token_reserves: u64,
+    pub max_virtual_token_reserves: u64,
+    pub min_token_supply: u64,
+    pub max_token_supply: u64,
+    pub min_decimals: u8,
+    pub max_decimals: u8,
+}
+
+impl LaunchLimits {
+    pub fn is_valid(&self) -> bool {
+        self.min_virtual_sol_reserves > 0
+            && self.min_virtual_sol_reserves <= self.max_virtual_sol_reserves
+            && self.min_virtual_token_reserves > 0
+            && self.min_virtual_token_reserves <= self.max_virtual_token_reserves
+            && self.min_token_supply > 0
+            && self.min_token_supply <= self.max_token_supply
+            && self.min_decimals <= self.max_decimals
+            && self.max_decimals <= MAX_TOKEN_DECIMALS
+    }
+
+    pub fn check(&self, params: &LaunchParams) -> Result<()> {
+        require!(
+            (self.min_virtual_sol_reserves..=self.max_virtual_sol_reserves)
+                .contains(&params.initial_virtual_sol_reserves)
+                && (self.min_virtual_token_reserves..=self.max_virtual_token_reserves)
+                    .contains(&params.initial_virtual_token_reserves)
+                && (self.min_token_supply..=self.max_token_supply).contains(&params.token_supply)
+                && (self.min_decimals..=self.max_decimals).contains(&params.decimals),
+            PumpError::LaunchParamsOutOfRange
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateGlobalConfigParams {
+    pub treasury: Option<Pubkey>,
+    pub buy_fee_bps: Option<u16>,
+    pub sell_fee_bps: Option<u16>,
+    pub creator_fee_bps: Option<u16>,
+    pub referral_fee_share_bps: Option<u16>,
+    pub creation_fee: Option<u64>,
+    pub graduation_threshold: Option<u64>,
+    pub max_price_impact_bps: Option<u16>,
+    pub graduation_timeout: Option<i64>,
+}
+
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub old_buy_fee_bps: u16,
+    pub new_buy_fee_bps: u16,
+    pub old_sell_fee_bps: u16,
+    pub new_sell_fee_bps: u16,
+    pub old_creator_fee_bps: u16,
+    pub new_creator_fee_bps: u16,
+    pub old_referral_fee_share_bps: u16,
+    pub new_referral_fee_share_bps: u16,
+    pub old_creation_fee: u64,
+    pub new_creation_fee: u64,
+    pub old_graduation_threshold: u64,
+    pub new_graduation_threshold: u64,
+    pub old_max_price_impact_bps: u16,
+    pub new_max_price_impact_bps: u16,
+    pub old_graduation_timeout: i64,
+    pub new_graduation_timeout: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigMigratedEvent {
+    pub authority: Pubkey,
+    pub old_len: u64,
+    pub new_len: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposedEvent {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityAcceptedEvent {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseUpdatedEvent {
+    pub account: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeTiersUpdatedEvent {
+    pub fee_tiers: Vec<FeeTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchLimitsUpdatedEvent {
+    pub old_limits: LaunchLimits,
+    pub new_limits: LaunchLimits,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1() -> GlobalConfigV1 {
+        GlobalConfigV1 {
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            buy_fee_bps: 150,
+            sell_fee_bps: 125,
+            creation_fee: 7,
+            graduation_threshold: 42,
+            total_tokens_created: 3,
+            total_volume_sol: u128::MAX,
+            allowed_relayer: Pubkey::new_unique(),
+            paused: true,
+            bump: 254,
+        }
+    }
+
+    #[test]
+    fn the_first_deployment_layout_is_a_prefix_of_the_current_one() {
+        let cfg = GlobalConfig::from(v1());
+        let bytes = cfg.try_to_vec().unwrap();
+        let prefix = GlobalConfigV1::deserialize(&mut &bytes[..]).unwrap();
+
+        assert_eq!(prefix.authority, cfg.authority);
+        assert_eq!(prefix.treasury, cfg.treasury);
+        assert_eq!(prefix.buy_fee_bps, cfg.buy_fee_bps);
+        assert_eq!(prefix.sell_fee_bps, cfg.sell_fee_bps);
+        assert_eq!(prefix.creation_fee, cfg.creation_fee);
+        assert_eq!(prefix.graduation_threshold, cfg.graduation_threshold);
+        assert_eq!(prefix.total_tokens_created, cfg.total_tokens_created);
+        assert_eq!(prefix.total_volume_sol, cfg.total_volume_sol);
+        assert_eq!(prefix.paused, cfg.paused);
+        assert_eq!(prefix.bump, cfg.bump);
+    }
+
+    #[test]
+    fn migrated_configs_start_from_the_defaults() {
+        let cfg = GlobalConfig::from(v1());
+
+        assert!(cfg.pending_authority.is_none());
+        assert_eq!(cfg.creator_fee_bps, DEFAULT_CREATOR_FEE_BPS);
+        assert_eq!(cfg.referral_fee_share_bps, DEFAULT_REFERRAL_FEE_SHARE_BPS);
+        assert_eq!(cfg.fee_tier_count, 0);
+        assert!(cfg.launch_limits.is_valid());
+        assert_eq!(cfg.max_price_impact_bps, DEFAULT_MAX_PRICE_IMPACT_BPS);
+        assert_eq!(cfg.graduation_timeout, DEFAULT_GRADUATION_TIMEOUT);
+        assert!(cfg.try_to_vec().unwrap().len() <= std::mem::size_of::<GlobalConfig>());
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct CreatorVault {
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    pub total_accrued: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+#[event]
+pub struct CreatorFeesClaimedEvent {
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
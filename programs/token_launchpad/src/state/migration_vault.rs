@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// Escrow for the reserves of a curve that has stopped trading. It holds the
+/// SOL as its own lamports and the tokens in its associated token account.
+/// `graduate` releases both straight into the pool; if the curve fails its
+/// deadline instead, the tokens are burned and `redeem` pays the SOL out to
+/// holders pro rata.
+#[account]
+pub struct MigrationVault {
+    pub bonding_curve: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    /// Unix time after which a still-`Pending` curve can be failed.
+    pub deadline: i64,
+    pub bump: u8,
+}
+
+#[event]
+pub struct GraduationFailedEvent {
+    pub bonding_curve: Pubkey,
+    pub token_mint: Pubkey,
+    pub sol_amount: u64,
+    pub burned_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemedEvent {
+    pub bonding_curve: Pubkey,
+    pub holder: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
@@ -1,4 +1,9 @@
 pub mod bonding_curve;
 pub mod config;
+pub mod creator_vault;
 pub mod graduate;
+pub mod migration_vault;
 pub mod pool_request;
+pub mod referral;
+pub mod trade;
+pub mod trader_stats;
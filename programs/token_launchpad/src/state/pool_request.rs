@@ -4,11 +4,9 @@ use anchor_lang::prelude::*;
 pub struct CreatePoolRequestEvent {
     pub bonding_curve: Pubkey,
     pub token_mint: Pubkey,
-    pub wsol_mint: Pubkey,
-    pub wsol_ata: Pubkey,
+    pub migration_vault: Pubkey,
     pub token_amount: u64,
     pub wsol_amount: u64,
-    pub token_ata: Pubkey,
     pub creator: Pubkey,
     pub timestamp: i64,
 }
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Referral {
+    pub referrer: Pubkey,
+    pub referred_volume_sol: u128,
+    pub total_earned: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+#[event]
+pub struct ReferralRewardsClaimedEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
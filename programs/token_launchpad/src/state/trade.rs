@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+/// Return data of `quote_buy` and `quote_sell`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TradeQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub new_price: u128,
+    pub price_impact_bps: u16,
+}
+
+/// Emitted when a buy is cut short at the graduation threshold. Only
+/// `filled_sol_amount` leaves the buyer's wallet.
+#[event]
+pub struct PartialFillEvent {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub requested_sol_amount: u64,
+    pub filled_sol_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeEvent {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub is_buy: bool,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct TraderStats {
+    pub trader: Pubkey,
+    pub total_volume_sol: u128,
+    pub trade_count: u64,
+    pub bump: u8,
+}
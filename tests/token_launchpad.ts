@@ -1,16 +1,1664 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SYSVAR_CLOCK_PUBKEY,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { Amm } from "../target/types/amm";
 import { TokenLaunchpad } from "../target/types/token_launchpad";
 
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+const DEFAULT_LAUNCH_PARAMS = {
+  initialVirtualSolReserves: new anchor.BN(30 * LAMPORTS_PER_SOL),
+  initialVirtualTokenReserves: new anchor.BN("1073000000000000"),
+  tokenSupply: new anchor.BN("1000000000000000"),
+  curveSupply: new anchor.BN("793100000000000"),
+  decimals: 6,
+};
+const ata = (mint: PublicKey, owner: PublicKey) =>
+  anchor.utils.token.associatedAddress({ mint, owner });
+// Associated token program `CreateIdempotent`.
+const createAtaIx = (payer: PublicKey, mint: PublicKey, owner: PublicKey) =>
+  new TransactionInstruction({
+    programId: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: ata(mint, owner), isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      {
+        pubkey: anchor.utils.token.TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+    ],
+    data: Buffer.from([1]),
+  });
+// Token program `SyncNative`.
+const syncNativeIx = (account: PublicKey) =>
+  new TransactionInstruction({
+    programId: anchor.utils.token.TOKEN_PROGRAM_ID,
+    keys: [{ pubkey: account, isSigner: false, isWritable: true }],
+    data: Buffer.from([17]),
+  });
+
 describe("token_launchpad", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.tokenLaunchpad as Program<TokenLaunchpad>;
+  const amm = anchor.workspace.amm as Program<Amm>;
+  const admin = provider.wallet as anchor.Wallet;
+  const treasury = Keypair.generate().publicKey;
+
+  const [globalConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("global-config")],
+    program.programId
+  );
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+
+  const airdrop = async (to: PublicKey, sol: number) => {
+    const sig = await provider.connection.requestAirdrop(
+      to,
+      sol * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig, "confirmed");
+  };
+
+  // `update_global_config` params that leave every setting as it is.
+  const unchanged = {
+    treasury: null,
+    buyFeeBps: null,
+    sellFeeBps: null,
+    creatorFeeBps: null,
+    referralFeeShareBps: null,
+    creationFee: null,
+    graduationThreshold: null,
+    maxPriceImpactBps: null,
+    graduationTimeout: null,
+  };
+
+  const initGlobalConfig = (
+    signer: PublicKey,
+    graduationThreshold = 85 * LAMPORTS_PER_SOL
+  ) =>
+    program.methods
+      .initGlobalConfig(treasury, new anchor.BN(graduationThreshold))
+      .accountsPartial({
+        admin: signer,
+        program: program.programId,
+        programData,
+        globalConfig,
+      });
+
+  const createToken = async (
+    creator: Keypair,
+    curveKind: object = { constantProduct: {} },
+    params = DEFAULT_LAUNCH_PARAMS
+  ) => {
+    const mint = Keypair.generate();
+    const [bondingCurve] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bonding-curve"),
+        mint.publicKey.toBuffer(),
+        creator.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const [metadataAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.publicKey.toBuffer(),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    const [creatorVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator-vault"), bondingCurve.toBuffer()],
+      program.programId
+    );
+    const [migrationVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("migration-vault"), bondingCurve.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createToken(
+        "Blow Up",
+        "BLOW",
+        "https://example.com/blow.json",
+        curveKind as never,
+        params
+      )
+      .accountsPartial({
+        creator: creator.publicKey,
+        globalConfig,
+        treasury,
+        tokenMint: mint.publicKey,
+        bondingCurve,
+        creatorVault,
+        bondingCurveTokenAccount: ata(mint.publicKey, bondingCurve),
+        migrationVault,
+        migrationVaultTokenAccount: ata(mint.publicKey, migrationVault),
+        metadataAccount,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([creator, mint])
+      .rpc();
+
+    return { mint: mint.publicKey, bondingCurve, creatorVault, migrationVault };
+  };
+
+  type Launch = Awaited<ReturnType<typeof createToken>>;
+
+  const traderStatsPda = (trader: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("trader-stats"), trader.toBuffer()],
+      program.programId
+    )[0];
+
+  const buyAccounts = (buyer: PublicKey, launch: Launch) => ({
+    buyer,
+    globalConfig,
+    treasury,
+    tokenMint: launch.mint,
+    bondingCurve: launch.bondingCurve,
+    creatorVault: launch.creatorVault,
+    bondingCurveTokenAccount: ata(launch.mint, launch.bondingCurve),
+    buyerTokenAccount: ata(launch.mint, buyer),
+    migrationVault: launch.migrationVault,
+    migrationVaultTokenAccount: ata(launch.mint, launch.migrationVault),
+    referral: null,
+    traderStats: traderStatsPda(buyer),
+  });
+
+  const sellAccounts = (seller: PublicKey, launch: Launch) => ({
+    seller,
+    tokenMint: launch.mint,
+    bondingCurve: launch.bondingCurve,
+    creatorVault: launch.creatorVault,
+    globalConfig,
+    treasury,
+    sellerTokenAccount: ata(launch.mint, seller),
+    bondingCurveTokenAccount: ata(launch.mint, launch.bondingCurve),
+    referral: null,
+    traderStats: traderStatsPda(seller),
+  });
+
+  const poolPda = (mint: PublicKey, depositor: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("pool"),
+        mint.toBuffer(),
+        WSOL_MINT.toBuffer(),
+        depositor.toBuffer(),
+      ],
+      amm.programId
+    )[0];
+
+  const graduateAccounts = (payer: PublicKey, launch: Launch) => {
+    const pool = poolPda(launch.mint, launch.migrationVault);
+    return {
+      tokenMint: launch.mint,
+      bondingCurve: launch.bondingCurve,
+      payer,
+      migrationVault: launch.migrationVault,
+      migrationVaultTokenAccount: ata(launch.mint, launch.migrationVault),
+      wsolMint: WSOL_MINT,
+      migrationVaultWsolAccount: ata(WSOL_MINT, launch.migrationVault),
+      pool,
+      poolTokenVault: ata(launch.mint, pool),
+      poolWsolVault: ata(WSOL_MINT, pool),
+      ammProgram: amm.programId,
+    };
+  };
+
+  const expectError = async (tx: Promise<unknown>, code: string) => {
+    let failed = false;
+    try {
+      await tx;
+    } catch (err) {
+      failed = true;
+      expect(String(err)).to.include(code);
+    }
+    expect(failed).to.eq(true);
+  };
+
+  describe("init_global_config", () => {
+    it("rejects a signer that is not the upgrade authority", async () => {
+      const attacker = Keypair.generate();
+      await airdrop(attacker.publicKey, 1);
+
+      let failed = false;
+      try {
+        await initGlobalConfig(attacker.publicKey).signers([attacker]).rpc();
+      } catch (err) {
+        failed = true;
+        expect(String(err)).to.include("NotAuthorized");
+      }
+      expect(failed).to.eq(true);
+    });
+
+    it("rejects a zero graduation threshold", async () => {
+      await expectError(
+        initGlobalConfig(admin.publicKey, 0).rpc(),
+        "IncorrectValue"
+      );
+    });
+
+    it("initializes once with the upgrade authority", async () => {
+      await initGlobalConfig(admin.publicKey).rpc();
+
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.authority.toBase58()).to.eq(admin.publicKey.toBase58());
+      expect(cfg.treasury.toBase58()).to.eq(treasury.toBase58());
+    });
+
+    it("fails when called a second time", async () => {
+      let failed = false;
+      try {
+        await initGlobalConfig(admin.publicKey).rpc();
+      } catch (err) {
+        failed = true;
+      }
+      expect(failed).to.eq(true);
+
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.authority.toBase58()).to.eq(admin.publicKey.toBase58());
+    });
+
+    it("cannot be re-run by another signer to seize authority", async () => {
+      const attacker = Keypair.generate();
+      await airdrop(attacker.publicKey, 1);
+
+      let failed = false;
+      try {
+        await initGlobalConfig(attacker.publicKey).signers([attacker]).rpc();
+      } catch (err) {
+        failed = true;
+      }
+      expect(failed).to.eq(true);
+
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.authority.toBase58()).to.eq(admin.publicKey.toBase58());
+    });
+  });
+
+  describe("trading fees", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+    });
+
+    it("routes the buy fee to the treasury", async () => {
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      const before = await provider.connection.getBalance(treasury);
+
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const after = await provider.connection.getBalance(treasury);
+      expect(after - before).to.eq((LAMPORTS_PER_SOL * cfg.buyFeeBps) / 10_000);
+    });
+
+    it("routes the sell fee to the treasury", async () => {
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+      const tokensIn = new anchor.BN(balance.value.amount);
+      const quote = await program.methods
+        .quoteSell(tokensIn)
+        .accountsPartial({
+          globalConfig,
+          bondingCurve: launch.bondingCurve,
+          trader: trader.publicKey,
+          traderStats: traderStatsPda(trader.publicKey),
+        })
+        .view();
+      const before = await provider.connection.getBalance(treasury);
+
+      await program.methods
+        .sellTokens(tokensIn, new anchor.BN(1))
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const grossSolOut = quote.amountOut.add(quote.fee);
+      const after = await provider.connection.getBalance(treasury);
+      expect(after - before).to.eq(
+        grossSolOut.muln(cfg.sellFeeBps).divn(10_000).toNumber()
+      );
+    });
+  });
+
+  describe("update_global_config", () => {
+    it("lets the authority re-configure within bounds", async () => {
+      await program.methods
+        .updateGlobalConfig({ ...unchanged, buyFeeBps: 200 })
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.buyFeeBps).to.eq(200);
+    });
+
+    it("rejects fees above the maximum", async () => {
+      let failed = false;
+      try {
+        await program.methods
+          .updateGlobalConfig({ ...unchanged, buyFeeBps: 10_000 })
+          .accountsPartial({ authority: admin.publicKey, globalConfig })
+          .rpc();
+      } catch (err) {
+        failed = true;
+        expect(String(err)).to.include("IncorrectValue");
+      }
+      expect(failed).to.eq(true);
+    });
+
+    for (const [field, value] of [
+      ["sellFeeBps", 10_000],
+      ["creatorFeeBps", 10_000],
+      ["referralFeeShareBps", 10_000],
+      ["creationFee", new anchor.BN(LAMPORTS_PER_SOL)],
+      ["graduationThreshold", new anchor.BN(0)],
+    ] as const) {
+      it(`rejects an out-of-bounds ${field}`, async () => {
+        await expectError(
+          program.methods
+            .updateGlobalConfig({ ...unchanged, [field]: value } as never)
+            .accountsPartial({ authority: admin.publicKey, globalConfig })
+            .rpc(),
+          "IncorrectValue"
+        );
+      });
+    }
+
+    it("rejects a signer that is not the authority", async () => {
+      const attacker = Keypair.generate();
+
+      await expectError(
+        program.methods
+          .updateGlobalConfig({ ...unchanged, buyFeeBps: 0 })
+          .accountsPartial({ authority: attacker.publicKey, globalConfig })
+          .signers([attacker])
+          .rpc(),
+        "NotAuthorized"
+      );
+
+      const cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.buyFeeBps).to.eq(200);
+    });
+  });
+
+  describe("authority transfer", () => {
+    it("requires the proposed key to accept", async () => {
+      const newAdmin = Keypair.generate();
+      const attacker = Keypair.generate();
+
+      await program.methods
+        .proposeAuthority(newAdmin.publicKey)
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+      let failed = false;
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accountsPartial({ newAuthority: attacker.publicKey, globalConfig })
+          .signers([attacker])
+          .rpc();
+      } catch (err) {
+        failed = true;
+        expect(String(err)).to.include("NotAuthorized");
+      }
+      expect(failed).to.eq(true);
+
+      await program.methods
+        .acceptAuthority()
+        .accountsPartial({ newAuthority: newAdmin.publicKey, globalConfig })
+        .signers([newAdmin])
+        .rpc();
+
+      let cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.authority.toBase58()).to.eq(newAdmin.publicKey.toBase58());
+      expect(cfg.pendingAuthority).to.eq(null);
+
+      // Hand authority back so later tests keep using the provider wallet.
+      await program.methods
+        .proposeAuthority(admin.publicKey)
+        .accountsPartial({ authority: newAdmin.publicKey, globalConfig })
+        .signers([newAdmin])
+        .rpc();
+      await program.methods
+        .acceptAuthority()
+        .accountsPartial({ newAuthority: admin.publicKey, globalConfig })
+        .rpc();
+
+      cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.authority.toBase58()).to.eq(admin.publicKey.toBase58());
+    });
+  });
+
+  describe("migrate_global_config", () => {
+    const migrate = (authority: Keypair) =>
+      program.methods
+        .migrateGlobalConfig()
+        .accountsPartial({ authority: authority.publicKey, globalConfig })
+        .signers([authority])
+        .rpc();
+
+    it("rejects a signer that is not the authority", async () => {
+      const attacker = Keypair.generate();
+      await airdrop(attacker.publicKey, 1);
+
+      await expectError(migrate(attacker), "NotAuthorized");
+    });
+
+    it("rejects a config already in the current layout", async () => {
+      await expectError(migrate(admin.payer), "ConfigAlreadyMigrated");
+    });
+  });
+
+  describe("set_paused", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    const setPaused = (paused: boolean) =>
+      program.methods
+        .setPaused(paused)
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+    const setCurvePaused = (paused: boolean) =>
+      program.methods
+        .setCurvePaused(paused)
+        .accountsPartial({
+          authority: admin.publicKey,
+          globalConfig,
+          tokenMint: launch.mint,
+          bondingCurve: launch.bondingCurve,
+        })
+        .rpc();
+
+    const buy = (target: Launch) =>
+      program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, target))
+        .signers([trader])
+        .rpc();
+
+    const sell = () =>
+      program.methods
+        .sellTokens(new anchor.BN(1_000_000), new anchor.BN(0))
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+      await buy(launch);
+    });
+
+    it("only lets the authority toggle the platform pause", async () => {
+      const attacker = Keypair.generate();
+
+      let failed = false;
+      try {
+        await program.methods
+          .setPaused(true)
+          .accountsPartial({ authority: attacker.publicKey, globalConfig })
+          .signers([attacker])
+          .rpc();
+      } catch (err) {
+        failed = true;
+        expect(String(err)).to.include("NotAuthorized");
+      }
+      expect(failed).to.eq(true);
+
+      await program.methods
+        .setPaused(true)
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+      let cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.paused).to.eq(true);
+
+      await program.methods
+        .setPaused(false)
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+      cfg = await program.account.globalConfig.fetch(globalConfig);
+      expect(cfg.paused).to.eq(false);
+    });
+
+    it("blocks launches and trades while the platform is paused", async () => {
+      await setPaused(true);
+      try {
+        await expectError(createToken(creator), "PlatformPaused");
+        await expectError(buy(launch), "PlatformPaused");
+        await expectError(sell(), "PlatformPaused");
+      } finally {
+        await setPaused(false);
+      }
+
+      await buy(launch);
+      await sell();
+    });
+
+    it("blocks trades on a paused curve only", async () => {
+      await setCurvePaused(true);
+      try {
+        await expectError(buy(launch), "CurvePaused");
+        await expectError(sell(), "CurvePaused");
+
+        const other = await createToken(creator);
+        await buy(other);
+      } finally {
+        await setCurvePaused(false);
+      }
+
+      await buy(launch);
+      await sell();
+    });
+  });
+
+  describe("slippage", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+    });
+
+    it("rejects a buy below min_tokens_out", async () => {
+      await expectError(
+        program.methods
+          .buyTokens(
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN("1000000000000000000")
+          )
+          .accountsPartial(buyAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc(),
+        "SlippageExceeded"
+      );
+    });
+
+    it("rejects a sell below min_sol_out", async () => {
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+
+      await expectError(
+        program.methods
+          .sellTokens(
+            new anchor.BN(balance.value.amount),
+            new anchor.BN(LAMPORTS_PER_SOL)
+          )
+          .accountsPartial(sellAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc(),
+        "SlippageExceeded"
+      );
+    });
+  });
+
+  describe("sell accounts", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+    });
+
+    it("rejects a curve token account the curve does not own", async () => {
+      const own = ata(launch.mint, trader.publicKey);
+      const balance = await provider.connection.getTokenAccountBalance(own);
+
+      await expectError(
+        program.methods
+          .sellTokens(new anchor.BN(balance.value.amount), new anchor.BN(0))
+          .accountsPartial({
+            ...sellAccounts(trader.publicKey, launch),
+            bondingCurveTokenAccount: own,
+          })
+          .signers([trader])
+          .rpc(),
+        "ConstraintAssociated"
+      );
+    });
+  });
+
+  describe("exact-output trades", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+    });
+
+    it("buys exactly the requested tokens", async () => {
+      const tokensOut = new anchor.BN(1_000_000).mul(new anchor.BN(1_000_000));
+
+      await program.methods
+        .buyExactTokens(tokensOut, new anchor.BN(LAMPORTS_PER_SOL))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+      expect(balance.value.amount).to.eq(tokensOut.toString());
+    });
+
+    it("sells for exactly the requested SOL", async () => {
+      const solOut = LAMPORTS_PER_SOL / 1000;
+      const before = await provider.connection.getBalance(trader.publicKey);
+
+      await program.methods
+        .sellForExactSol(new anchor.BN(solOut), new anchor.BN("1000000000000"))
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const after = await provider.connection.getBalance(trader.publicKey);
+      expect(after - before).to.eq(solOut);
+    });
+
+    it("rejects a buy above max_sol_in", async () => {
+      await expectError(
+        program.methods
+          .buyExactTokens(new anchor.BN(1_000_000), new anchor.BN(0))
+          .accountsPartial(buyAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc(),
+        "SlippageExceeded"
+      );
+    });
+  });
+
+  describe("creator fees", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+    });
+
+    it("accrues on trades and is claimable only by the creator", async () => {
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const vault = await program.account.creatorVault.fetch(
+        launch.creatorVault
+      );
+      expect(vault.totalAccrued.toNumber()).to.be.greaterThan(0);
+
+      await expectError(
+        program.methods
+          .claimCreatorFees()
+          .accountsPartial({
+            creator: trader.publicKey,
+            tokenMint: launch.mint,
+            bondingCurve: launch.bondingCurve,
+            creatorVault: launch.creatorVault,
+          })
+          .signers([trader])
+          .rpc(),
+        "NotAuthorized"
+      );
+
+      const before = await provider.connection.getBalance(creator.publicKey);
+      await program.methods
+        .claimCreatorFees()
+        .accountsPartial({
+          creator: creator.publicKey,
+          tokenMint: launch.mint,
+          bondingCurve: launch.bondingCurve,
+          creatorVault: launch.creatorVault,
+        })
+        .signers([creator])
+        .rpc();
+      const after = await provider.connection.getBalance(creator.publicKey);
+
+      expect(after - before).to.eq(vault.totalAccrued.toNumber());
+    });
+  });
+
+  describe("referrals", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    const referrer = Keypair.generate();
+    let launch: Launch;
+    let referral: PublicKey;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      await airdrop(referrer.publicKey, 1);
+      launch = await createToken(creator);
+
+      [referral] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), referrer.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .createReferral()
+        .accountsPartial({ referrer: referrer.publicKey, referral })
+        .signers([referrer])
+        .rpc();
+    });
+
+    it("rejects self-referral", async () => {
+      await expectError(
+        program.methods
+          .buyTokens(new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0))
+          .accountsPartial({
+            ...buyAccounts(referrer.publicKey, launch),
+            referral,
+          })
+          .signers([referrer])
+          .rpc(),
+        "SelfReferral"
+      );
+    });
+
+    it("pays the referrer a share of the platform fee", async () => {
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0))
+        .accountsPartial({
+          ...buyAccounts(trader.publicKey, launch),
+          referral,
+        })
+        .signers([trader])
+        .rpc();
+
+      const account = await program.account.referral.fetch(referral);
+      expect(account.totalEarned.toNumber()).to.be.greaterThan(0);
+      expect(account.referredVolumeSol.toString()).to.eq(
+        String(LAMPORTS_PER_SOL)
+      );
+
+      const before = await provider.connection.getBalance(referrer.publicKey);
+      await program.methods
+        .claimReferralRewards()
+        .accountsPartial({ referrer: referrer.publicKey, referral })
+        .signers([referrer])
+        .rpc();
+      const after = await provider.connection.getBalance(referrer.publicKey);
+
+      expect(after - before).to.eq(account.totalEarned.toNumber());
+    });
+  });
+
+  describe("fee tiers", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+    });
+
+    after(async () => {
+      await program.methods
+        .setFeeTiers([])
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+    });
+
+    it("rejects tiers that are not ascending", async () => {
+      await expectError(
+        program.methods
+          .setFeeTiers([
+            { volumeThresholdSol: new anchor.BN(10), discountBps: 1000 },
+            { volumeThresholdSol: new anchor.BN(5), discountBps: 2000 },
+          ])
+          .accountsPartial({ authority: admin.publicKey, globalConfig })
+          .rpc(),
+        "IncorrectValue"
+      );
+    });
+
+    it("discounts the platform fee once the threshold is crossed", async () => {
+      await program.methods
+        .setFeeTiers([
+          {
+            volumeThresholdSol: new anchor.BN(LAMPORTS_PER_SOL),
+            discountBps: 10_000,
+          },
+        ])
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+      const buy = () =>
+        program.methods
+          .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0))
+          .accountsPartial(buyAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc();
+
+      let before = await provider.connection.getBalance(treasury);
+      await buy();
+      expect(await provider.connection.getBalance(treasury)).to.be.greaterThan(
+        before
+      );
+
+      const stats = await program.account.traderStats.fetch(
+        traderStatsPda(trader.publicKey)
+      );
+      expect(stats.tradeCount.toNumber()).to.eq(1);
+
+      before = await provider.connection.getBalance(treasury);
+      await buy();
+      expect(await provider.connection.getBalance(treasury)).to.eq(before);
+    });
+
+    it("quotes the discount only for the trader who earned it", async () => {
+      const quote = (wallet: PublicKey, traderStats: PublicKey | null) =>
+        program.methods
+          .quoteBuy(new anchor.BN(LAMPORTS_PER_SOL))
+          .accountsPartial({
+            globalConfig,
+            bondingCurve: launch.bondingCurve,
+            trader: wallet,
+            traderStats,
+          })
+          .view();
+
+      const discounted = await quote(
+        trader.publicKey,
+        traderStatsPda(trader.publicKey)
+      );
+      const full = await quote(trader.publicKey, null);
+      expect(discounted.fee.lt(full.fee)).to.eq(true);
+
+      await expectError(
+        quote(Keypair.generate().publicKey, traderStatsPda(trader.publicKey)),
+        "ConstraintSeeds"
+      );
+    });
+  });
+
+  describe("curve shapes", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+
+    before(async () => {
+      await airdrop(creator.publicKey, 5);
+      await airdrop(trader.publicKey, 10);
+    });
+
+    // The curve stores its kind as the enum's discriminant.
+    const kinds = [{ linear: {} }, { exponential: {} }];
+    for (const [index, curveKind] of kinds.entries()) {
+      it(`buys and sells on a ${Object.keys(curveKind)[0]} curve`, async () => {
+        const launch = await createToken(creator, curveKind, {
+          ...DEFAULT_LAUNCH_PARAMS,
+          curveSupply: new anchor.BN("500000000000000"),
+        });
+
+        await program.methods
+          .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+          .accountsPartial(buyAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc();
+
+        const curve = await program.account.bondingCurve.fetch(
+          launch.bondingCurve
+        );
+        expect(curve.curveKind).to.eq(index + 1);
+
+        const balance = await provider.connection.getTokenAccountBalance(
+          ata(launch.mint, trader.publicKey)
+        );
+        await program.methods
+          .sellTokens(new anchor.BN(balance.value.amount), new anchor.BN(1))
+          .accountsPartial(sellAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc();
+      });
+    }
+  });
+
+  describe("launch limits", () => {
+    const creator = Keypair.generate();
+    const limits = {
+      minVirtualSolReserves: new anchor.BN(LAMPORTS_PER_SOL),
+      maxVirtualSolReserves: new anchor.BN(10_000 * LAMPORTS_PER_SOL),
+      minVirtualTokenReserves: new anchor.BN("1000000000000"),
+      maxVirtualTokenReserves: new anchor.BN("10000000000000000000"),
+      minTokenSupply: new anchor.BN("1000000000000"),
+      maxTokenSupply: new anchor.BN("10000000000000000000"),
+      minDecimals: 0,
+      maxDecimals: 9,
+    };
+
+    before(async () => {
+      await airdrop(creator.publicKey, 5);
+    });
+
+    after(async () => {
+      await program.methods
+        .setLaunchLimits(limits)
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+    });
+
+    it("rejects limits whose minimum exceeds the maximum", async () => {
+      await expectError(
+        program.methods
+          .setLaunchLimits({ ...limits, minDecimals: 9, maxDecimals: 6 })
+          .accountsPartial({ authority: admin.publicKey, globalConfig })
+          .rpc(),
+        "IncorrectValue"
+      );
+    });
+
+    it("creates a launch with custom reserves and decimals", async () => {
+      const params = {
+        initialVirtualSolReserves: new anchor.BN(60 * LAMPORTS_PER_SOL),
+        initialVirtualTokenReserves: new anchor.BN("1073000000000000000"),
+        tokenSupply: new anchor.BN("1000000000000000000"),
+        curveSupply: new anchor.BN("793100000000000000"),
+        decimals: 9,
+      };
+      const launch = await createToken(
+        creator,
+        { constantProduct: {} },
+        params
+      );
+
+      const curve = await program.account.bondingCurve.fetch(
+        launch.bondingCurve
+      );
+      expect(curve.virtualSolReserves.toString()).to.eq(
+        params.initialVirtualSolReserves.toString()
+      );
+      expect(curve.realTokenReserves.toString()).to.eq(
+        params.curveSupply.toString()
+      );
+      expect(curve.lpTokenReserves.toString()).to.eq(
+        params.tokenSupply.sub(params.curveSupply).toString()
+      );
+
+      const mint = await provider.connection.getParsedAccountInfo(
+        launch.mint
+      );
+      expect(
+        (mint.value.data as anchor.web3.ParsedAccountData).parsed.info.decimals
+      ).to.eq(9);
+    });
+
+    it("rejects an LP tranche too small for the final price", async () => {
+      const params = {
+        ...DEFAULT_LAUNCH_PARAMS,
+        curveSupply: new anchor.BN("900000000000000"),
+      };
+      await expectError(
+        createToken(creator, { constantProduct: {} }, params),
+        "InvalidSupplyAllocation"
+      );
+    });
+
+    it("rejects launch parameters outside the limits", async () => {
+      await program.methods
+        .setLaunchLimits({
+          ...limits,
+          maxVirtualSolReserves: new anchor.BN(10 * LAMPORTS_PER_SOL),
+        })
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+      await expectError(createToken(creator), "LaunchParamsOutOfRange");
+    });
+  });
+
+  describe("quotes", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+    });
+
+    it("quotes a buy exactly as it executes", async () => {
+      const quote = await program.methods
+        .quoteBuy(new anchor.BN(LAMPORTS_PER_SOL))
+        .accountsPartial({
+          globalConfig,
+          bondingCurve: launch.bondingCurve,
+          trader: trader.publicKey,
+          traderStats: null,
+        })
+        .view();
+      expect(quote.priceImpactBps).to.be.greaterThan(0);
+
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), quote.amountOut)
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+      expect(balance.value.amount).to.eq(quote.amountOut.toString());
+    });
+
+    it("quotes a sell against the current reserves", async () => {
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+      const quote = await program.methods
+        .quoteSell(new anchor.BN(balance.value.amount))
+        .accountsPartial({
+          globalConfig,
+          bondingCurve: launch.bondingCurve,
+          trader: trader.publicKey,
+          traderStats: traderStatsPda(trader.publicKey),
+        })
+        .view();
+
+      await program.methods
+        .sellTokens(new anchor.BN(balance.value.amount), quote.amountOut)
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+    });
+  });
+
+  describe("graduation partial fill", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    // Graduation is permissionless, so any funded key can crank it.
+    const keeper = Keypair.generate();
+    let launch: Launch;
+
+    const setGraduationThreshold = (lamports: number) =>
+      program.methods
+        .updateGlobalConfig({
+          ...unchanged,
+          graduationThreshold: new anchor.BN(lamports),
+        })
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 10);
+      await airdrop(keeper.publicKey, 1);
+      launch = await createToken(creator);
+      await setGraduationThreshold(2 * LAMPORTS_PER_SOL);
+    });
+
+    after(async () => {
+      await setGraduationThreshold(85 * LAMPORTS_PER_SOL);
+    });
+
+    it("rejects an exact-token buy past the threshold", async () => {
+      const tokensOut = new anchor.BN(500_000_000).mul(
+        new anchor.BN(1_000_000)
+      );
+
+      await expectError(
+        program.methods
+          .buyExactTokens(tokensOut, new anchor.BN(10 * LAMPORTS_PER_SOL))
+          .accountsPartial(buyAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc(),
+        "GraduationThresholdExceeded"
+      );
+    });
+
+    it("only takes the SOL needed to reach the threshold", async () => {
+      const before = await provider.connection.getBalance(trader.publicKey);
+
+      await program.methods
+        .buyTokens(new anchor.BN(5 * LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const after = await provider.connection.getBalance(trader.publicKey);
+      expect(before - after).to.be.lessThan(3 * LAMPORTS_PER_SOL);
+
+      const curve = await program.account.bondingCurve.fetch(
+        launch.bondingCurve
+      );
+      // GraduationState::Pending
+      expect(curve.graduated).to.eq(1);
+      expect(curve.realSolReserves.toNumber()).to.eq(0);
+
+      const vault = await program.account.migrationVault.fetch(
+        launch.migrationVault
+      );
+      expect(vault.solAmount.toNumber()).to.be.greaterThan(0);
+      expect(vault.tokenAmount.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("is not blocked by pools or vaults created ahead of it", async () => {
+      const squat = poolPda(launch.mint, trader.publicKey);
+      const launchPool = poolPda(launch.mint, launch.migrationVault);
+      const traderWsol = ata(WSOL_MINT, trader.publicKey);
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          createAtaIx(trader.publicKey, WSOL_MINT, trader.publicKey),
+          SystemProgram.transfer({
+            fromPubkey: trader.publicKey,
+            toPubkey: traderWsol,
+            lamports: 1_000_000,
+          }),
+          syncNativeIx(traderWsol),
+          createAtaIx(trader.publicKey, launch.mint, launchPool),
+          createAtaIx(trader.publicKey, WSOL_MINT, launchPool)
+        ),
+        [trader]
+      );
+      await amm.methods
+        .initializePool(new anchor.BN(1_000_000), new anchor.BN(1_000_000))
+        .accountsPartial({
+          payer: trader.publicKey,
+          depositor: trader.publicKey,
+          tokenAMint: launch.mint,
+          tokenBMint: WSOL_MINT,
+          pool: squat,
+          tokenAVault: ata(launch.mint, squat),
+          tokenBVault: ata(WSOL_MINT, squat),
+          depositorTokenA: ata(launch.mint, trader.publicKey),
+          depositorTokenB: traderWsol,
+        })
+        .signers([trader])
+        .rpc();
+
+      const pool = await amm.account.pool.fetch(squat);
+      expect(pool.depositor.toBase58()).to.eq(trader.publicKey.toBase58());
+    });
+
+    // The pool's seeds constraint rejects any other pool before the CPI, so
+    // graduate's InvalidPool and PoolReservesMismatch checks after it are
+    // defensive only and cannot be reached from here.
+    it("rejects a pool that is not the launch's AMM pool", async () => {
+      const pool = poolPda(launch.mint, trader.publicKey);
+
+      await expectError(
+        program.methods
+          .graduate()
+          .accountsPartial({
+            ...graduateAccounts(keeper.publicKey, launch),
+            pool,
+            poolTokenVault: ata(launch.mint, pool),
+            poolWsolVault: ata(WSOL_MINT, pool),
+          })
+          .signers([keeper])
+          .rpc(),
+        "ConstraintSeeds"
+      );
+    });
+
+    it("releases the escrowed reserves into the AMM pool", async () => {
+      const escrow = await program.account.migrationVault.fetch(
+        launch.migrationVault
+      );
+
+      await program.methods
+        .graduate()
+        .accountsPartial(graduateAccounts(keeper.publicKey, launch))
+        .signers([keeper])
+        .rpc();
+
+      const curve = await program.account.bondingCurve.fetch(
+        launch.bondingCurve
+      );
+      const pool = poolPda(launch.mint, launch.migrationVault);
+      // GraduationState::Graduated
+      expect(curve.graduated).to.eq(2);
+      expect(curve.pool.toBase58()).to.eq(pool.toBase58());
+
+      const poolAccount = await amm.account.pool.fetch(pool);
+      const tokenVault = await provider.connection.getTokenAccountBalance(
+        poolAccount.tokenAVault
+      );
+      const wsolVault = await provider.connection.getTokenAccountBalance(
+        poolAccount.tokenBVault
+      );
+      expect(tokenVault.value.amount).to.eq(escrow.tokenAmount.toString());
+      expect(wsolVault.value.amount).to.eq(escrow.solAmount.toString());
+
+      const vault = await program.account.migrationVault.fetch(
+        launch.migrationVault
+      );
+      expect(vault.solAmount.toNumber()).to.eq(0);
+      expect(vault.tokenAmount.toNumber()).to.eq(0);
+    });
+
+    it("trades on the AMM pool once graduated", async () => {
+      const pool = poolPda(launch.mint, launch.migrationVault);
+      const traderTokens = ata(launch.mint, trader.publicKey);
+      const traderWsol = ata(WSOL_MINT, trader.publicKey);
+      const balance = async (account: PublicKey) =>
+        new anchor.BN(
+          (
+            await provider.connection.getTokenAccountBalance(account)
+          ).value.amount
+        );
+      const swap = (minAmountOut: anchor.BN) =>
+        amm.methods
+          .swap(new anchor.BN(1_000_000_000), minAmountOut, true)
+          .accountsPartial({
+            user: trader.publicKey,
+            pool,
+            tokenAVault: ata(launch.mint, pool),
+            tokenBVault: ata(WSOL_MINT, pool),
+            userTokenA: traderTokens,
+            userTokenB: traderWsol,
+          })
+          .signers([trader])
+          .rpc();
+
+      await expectError(
+        swap(new anchor.BN(LAMPORTS_PER_SOL)),
+        "SlippageExceeded"
+      );
+
+      const tokensBefore = await balance(traderTokens);
+      const wsolBefore = await balance(traderWsol);
+      await swap(new anchor.BN(1));
+
+      expect(tokensBefore.sub(await balance(traderTokens)).toString()).to.eq(
+        "1000000000"
+      );
+      expect((await balance(traderWsol)).gt(wsolBefore)).to.eq(true);
+    });
+
+    it("graduates a curve the threshold was lowered under", async () => {
+      const live = await createToken(creator);
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, live))
+        .signers([trader])
+        .rpc();
+      const bought = await provider.connection.getTokenAccountBalance(
+        ata(live.mint, trader.publicKey)
+      );
+
+      await setGraduationThreshold(LAMPORTS_PER_SOL / 2);
+      try {
+        await program.methods
+          .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+          .accountsPartial(buyAccounts(trader.publicKey, live))
+          .signers([trader])
+          .rpc();
+      } finally {
+        await setGraduationThreshold(2 * LAMPORTS_PER_SOL);
+      }
+
+      const curve = await program.account.bondingCurve.fetch(
+        live.bondingCurve
+      );
+      // GraduationState::Pending
+      expect(curve.graduated).to.eq(1);
+
+      // The buy only graduated the curve; it did not fill.
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(live.mint, trader.publicKey)
+      );
+      expect(balance.value.amount).to.eq(bought.value.amount);
+    });
+  });
+
+  describe("sell_percent", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+    });
+
+    const tokenBalance = async () =>
+      new anchor.BN(
+        (
+          await provider.connection.getTokenAccountBalance(
+            ata(launch.mint, trader.publicKey)
+          )
+        ).value.amount
+      );
+
+    it("sells the requested share of the balance", async () => {
+      const before = await tokenBalance();
+
+      await program.methods
+        .sellPercent(5_000, new anchor.BN(0))
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const after = await tokenBalance();
+      expect(after.toString()).to.eq(before.sub(before.divn(2)).toString());
+    });
+
+    it("rejects more than 100%", async () => {
+      await expectError(
+        program.methods
+          .sellPercent(10_001, new anchor.BN(0))
+          .accountsPartial(sellAccounts(trader.publicKey, launch))
+          .signers([trader])
+          .rpc(),
+        "IncorrectValue"
+      );
+    });
+
+    it("closes the token account when selling everything", async () => {
+      await program.methods
+        .sellPercent(10_000, new anchor.BN(0))
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+      const account = await provider.connection.getAccountInfo(
+        ata(launch.mint, trader.publicKey)
+      );
+      expect(account).to.eq(null);
+    });
+  });
+
+  describe("price impact limit", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    const setMaxPriceImpact = (bps: number) =>
+      program.methods
+        .updateGlobalConfig({ ...unchanged, maxPriceImpactBps: bps })
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+    const buy = () =>
+      program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+      await setMaxPriceImpact(100);
+    });
+
+    after(async () => {
+      // u16::MAX, the default, disables the limit.
+      await setMaxPriceImpact(65_535);
+    });
+
+    it("rejects a buy that moves the price too far", async () => {
+      await expectError(buy(), "PriceImpactExceeded");
+    });
+
+    it("honors a per-curve override", async () => {
+      await program.methods
+        .setCurveMaxPriceImpact(2_000)
+        .accountsPartial({
+          authority: admin.publicKey,
+          globalConfig,
+          tokenMint: launch.mint,
+          bondingCurve: launch.bondingCurve,
+        })
+        .rpc();
+
+      await buy();
+    });
+  });
+
+  describe("compute budget", () => {
+    // Upper bounds for the hot trade paths; a regression past these means
+    // something expensive (logging, extra (de)serialization) crept back in.
+    const BUY_CU_BUDGET = 100_000;
+    const SELL_CU_BUDGET = 100_000;
+
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    let launch: Launch;
+
+    const unitsConsumed = async (tx: anchor.web3.Transaction) => {
+      tx.feePayer = trader.publicKey;
+      tx.recentBlockhash = (
+        await provider.connection.getLatestBlockhash()
+      ).blockhash;
+      tx.sign(trader);
+      const { value } = await provider.connection.simulateTransaction(tx);
+      expect(value.err).to.eq(null);
+      return value.unitsConsumed;
+    };
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 5);
+      launch = await createToken(creator);
+
+      // The first buy pays for creating the trader's accounts.
+      await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+    });
+
+    it("buys within the compute budget", async () => {
+      const tx = await program.methods
+        .buyTokens(new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .transaction();
+
+      expect(await unitsConsumed(tx)).to.be.below(BUY_CU_BUDGET);
+    });
+
+    it("sells within the compute budget", async () => {
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+      const tx = await program.methods
+        .sellTokens(
+          new anchor.BN(balance.value.amount).divn(2),
+          new anchor.BN(1)
+        )
+        .accountsPartial(sellAccounts(trader.publicKey, launch))
+        .transaction();
+
+      expect(await unitsConsumed(tx)).to.be.below(SELL_CU_BUDGET);
+    });
+  });
+
+  describe("graduation deadline", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+
+    const setGraduation = (lamports: number, timeout: number) =>
+      program.methods
+        .updateGlobalConfig({
+          ...unchanged,
+          graduationThreshold: new anchor.BN(lamports),
+          graduationTimeout: new anchor.BN(timeout),
+        })
+        .accountsPartial({ authority: admin.publicKey, globalConfig })
+        .rpc();
+
+    // Buys past the lowered threshold so the curve is left `Pending`.
+    const launchPending = async () => {
+      const launch = await createToken(creator);
+      await program.methods
+        .buyTokens(new anchor.BN(3 * LAMPORTS_PER_SOL), new anchor.BN(1))
+        .accountsPartial(buyAccounts(trader.publicKey, launch))
+        .signers([trader])
+        .rpc();
+      return launch;
+    };
+
+    const graduationFailedAccounts = (launch: Launch) => ({
+      tokenMint: launch.mint,
+      bondingCurve: launch.bondingCurve,
+      migrationVault: launch.migrationVault,
+      migrationVaultTokenAccount: ata(launch.mint, launch.migrationVault),
+    });
+
+    // Waits on the cluster clock, which the program checks, rather than on
+    // wall time.
+    const waitPastDeadline = async (launch: Launch) => {
+      const { deadline } = await program.account.migrationVault.fetch(
+        launch.migrationVault
+      );
+      for (;;) {
+        const clock = await provider.connection.getAccountInfo(
+          SYSVAR_CLOCK_PUBKEY
+        );
+        // `Clock::unix_timestamp`
+        const now = new anchor.BN(clock.data.subarray(32, 40), "le");
+        if (now.gt(deadline)) return;
+        await new Promise((resolve) => setTimeout(resolve, 400));
+      }
+    };
+
+    before(async () => {
+      await airdrop(creator.publicKey, 2);
+      await airdrop(trader.publicKey, 10);
+    });
+
+    after(async () => {
+      await setGraduation(85 * LAMPORTS_PER_SOL, 3 * 24 * 60 * 60);
+    });
+
+    it("cannot fail a graduation before its deadline", async () => {
+      await setGraduation(2 * LAMPORTS_PER_SOL, 3 * 24 * 60 * 60);
+      const launch = await launchPending();
+
+      await expectError(
+        program.methods
+          .graduationFailed()
+          .accountsPartial(graduationFailedAccounts(launch))
+          .rpc(),
+        "GraduationDeadlineNotReached"
+      );
+    });
+
+    it("lets holders redeem the escrowed SOL once it has", async () => {
+      await setGraduation(2 * LAMPORTS_PER_SOL, 1);
+      const launch = await launchPending();
+      await waitPastDeadline(launch);
+
+      await program.methods
+        .graduationFailed()
+        .accountsPartial(graduationFailedAccounts(launch))
+        .rpc();
+
+      const curve = await program.account.bondingCurve.fetch(
+        launch.bondingCurve
+      );
+      // GraduationState::Failed
+      expect(curve.graduated).to.eq(3);
+
+      const escrow = await program.account.migrationVault.fetch(
+        launch.migrationVault
+      );
+      const balance = await provider.connection.getTokenAccountBalance(
+        ata(launch.mint, trader.publicKey)
+      );
+      const before = await provider.connection.getBalance(trader.publicKey);
+
+      await program.methods
+        .redeem(new anchor.BN(balance.value.amount))
+        .accountsPartial({
+          holder: trader.publicKey,
+          tokenMint: launch.mint,
+          bondingCurve: launch.bondingCurve,
+          migrationVault: launch.migrationVault,
+          holderTokenAccount: ata(launch.mint, trader.publicKey),
+        })
+        .signers([trader])
+        .rpc();
+
+      // The trader is the only holder, so they get the whole escrow.
+      const after = await provider.connection.getBalance(trader.publicKey);
+      expect(after - before).to.be.greaterThan(
+        escrow.solAmount.toNumber() - 10_000
+      );
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+      const vault = await program.account.migrationVault.fetch(
+        launch.migrationVault
+      );
+      expect(vault.solAmount.toNumber()).to.eq(0);
+    });
   });
 });